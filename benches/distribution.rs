@@ -0,0 +1,48 @@
+//! Timings of the distribution sorts on dense, near-uniform data.
+//!
+//! Run with `cargo bench --bench distribution`.
+
+use buldak::{counting, flash, pigeonhole, radix};
+
+const LEN: usize = 1_000_000;
+const RANGE: i32 = 10_000;
+const ROUNDS: u32 = 5;
+
+fn bench<F>(name: &str, input: &[i32], sort: F)
+where
+    F: Fn(&mut [i32]),
+{
+    let mut expected = input.to_vec();
+    expected.sort_unstable();
+
+    let mut total = std::time::Duration::default();
+    for _ in 0..ROUNDS {
+        let mut array = input.to_vec();
+        let start = std::time::Instant::now();
+        sort(&mut array);
+        total += start.elapsed();
+        assert_eq!(array, expected, "{} sorted wrong", name);
+    }
+
+    println!("{:<24} {:>10.2?}", name, total / ROUNDS);
+}
+
+fn main() {
+    // xorshift, so every run sorts the same telemetry-like values
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let input: Vec<i32> = (0..LEN)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % RANGE as u64) as i32 - RANGE / 2
+        })
+        .collect();
+
+    println!("{} values in {}..{}", LEN, -RANGE / 2, RANGE / 2);
+    bench("pigeonhole::sort", &input, |a| pigeonhole::sort(a).unwrap());
+    bench("flash::sort", &input, |a| flash::sort(a).unwrap());
+    bench("counting::sort", &input, |a| counting::sort(a).unwrap());
+    bench("radix::sort", &input, |a| radix::sort(a, 10).unwrap());
+    bench("radix::sort_keys", &input, radix::sort_keys);
+}
@@ -0,0 +1,67 @@
+//! Timings of the partial sorts and top-k selections against full intro sorts.
+//!
+//! Run with `cargo bench --bench partial`.
+
+use buldak::{intro, partial};
+
+const LEN: usize = 1_000_000;
+const K: usize = 100;
+const ROUNDS: u32 = 5;
+
+fn bench<F>(name: &str, input: &[u64], expected: &[u64], top: F)
+where
+    F: Fn(&[u64]) -> Vec<u64>,
+{
+    let mut total = std::time::Duration::default();
+    for _ in 0..ROUNDS {
+        let start = std::time::Instant::now();
+        let actual = top(input);
+        total += start.elapsed();
+        assert_eq!(actual, expected, "{} selected wrong", name);
+    }
+
+    println!("{:<28} {:>10.2?}", name, total / ROUNDS);
+}
+
+fn main() {
+    // xorshift, so every run ranks the same rows
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let input: Vec<u64> = (0..LEN)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state % 1_000_000_007
+        })
+        .collect();
+
+    let mut expected = input.clone();
+    expected.sort_unstable_by(|l, r| r.cmp(l));
+    expected.truncate(K);
+
+    let descending = |l: &u64, r: &u64| r.cmp(l);
+
+    println!("top {} of {} values", K, LEN);
+    bench("intro::sort_by", &input, &expected, |input| {
+        let mut array = input.to_vec();
+        intro::sort_by(&mut array, descending);
+        array.truncate(K);
+        array
+    });
+    bench("partial::partial_sort_by", &input, &expected, |input| {
+        let mut array = input.to_vec();
+        partial::partial_sort_by(&mut array, K, descending);
+        array.truncate(K);
+        array
+    });
+    bench("partial::top_k_by", &input, &expected, |input| {
+        partial::top_k_by(input.iter().copied(), K, |l, r| l.cmp(r))
+    });
+    bench("partial::TopK", &input, &expected, |input| {
+        let mut top = partial::TopK::new(K, |l: &u64, r: &u64| l.cmp(r));
+        for &e in input {
+            top.push(e);
+        }
+        top.into_sorted_vec()
+    });
+}
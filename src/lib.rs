@@ -40,6 +40,8 @@ fn main()
 - comb sort
 - cycle sort
 - oddeven sort
+- bitonic sort
+- odd-even merge sort
 - quick sort
 - merge sort
 - heap sort
@@ -47,11 +49,26 @@ fn main()
 - tim sort
 - counting sort
 - radix sort
+- msd radix sort
+- multikey quicksort, burstsort
+- bucket sort
+- pigeonhole sort
+- flash sort
+- spread sort
 - shell sort
 - bogo sort
 - sleep sort
 - stalin sort
+- drop-merge sort
 - pancake sort
+- parallel merge sort
+- parallel sample sort
+- sorting networks
+- sorted vector collection
+- selection: introselect, median of medians, Floyd-Rivest
+- partial sort, top-k
+- argsort
+- sorting parallel slices together
 - ... more later
 
 ## link
@@ -104,6 +121,9 @@ pub mod oddeven;
 #[path = "lib/bitonic.rs"]
 pub mod bitonic;
 
+#[path = "lib/oddeven_merge.rs"]
+pub mod oddeven_merge;
+
 #[path = "lib/quick.rs"]
 pub mod quick;
 
@@ -125,6 +145,24 @@ pub mod counting;
 #[path = "lib/radix.rs"]
 pub mod radix;
 
+#[path = "lib/msd_radix.rs"]
+pub mod msd_radix;
+
+#[path = "lib/string_sort.rs"]
+pub mod string_sort;
+
+#[path = "lib/bucket.rs"]
+pub mod bucket;
+
+#[path = "lib/pigeonhole.rs"]
+pub mod pigeonhole;
+
+#[path = "lib/flash.rs"]
+pub mod flash;
+
+#[path = "lib/spread.rs"]
+pub mod spread;
+
 #[path = "lib/bogo.rs"]
 pub mod bogo;
 
@@ -134,5 +172,29 @@ pub mod sleep;
 #[path = "lib/stalin.rs"]
 pub mod stalin;
 
+#[path = "lib/drop_merge.rs"]
+pub mod drop_merge;
+
 #[path = "lib/pancake.rs"]
 pub mod pancake;
+
+#[path = "lib/parallel.rs"]
+pub mod parallel;
+
+#[path = "lib/network.rs"]
+pub mod network;
+
+#[path = "lib/sorted_vec.rs"]
+pub mod sorted_vec;
+
+#[path = "lib/select.rs"]
+pub mod select;
+
+#[path = "lib/partial.rs"]
+pub mod partial;
+
+#[path = "lib/argsort.rs"]
+pub mod argsort;
+
+#[path = "lib/zipped.rs"]
+pub mod zipped;
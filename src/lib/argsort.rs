@@ -0,0 +1,449 @@
+//! argsort and permutations.
+//!
+//! Returns the indices that would sort an array instead of sorting it,
+//! so that other arrays can be reordered the same way.
+
+/// The sort algorithm an argsort runs on the indices.
+///
+/// Only the comparison sorts that keep every element are here.
+/// The sorts that work on keys instead of a comparator
+/// (gravity, counting, radix, msd_radix, string_sort, bucket, pigeonhole, flash, spread and sleep)
+/// have nothing to compare the indices with, and stalin sort drops elements,
+/// so their order can't be read as a permutation.
+/// The parallel sorts are in [`ParallelAlgorithm`], for [`par_argsort_by`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    Bubble,
+    SmartBubble,
+    CocktailShaker,
+    Selection,
+    DoubleSelection,
+    Insertion,
+    BinaryInsertion,
+    Shell,
+    Stooge,
+    Gnome,
+    Comb,
+    Cycle,
+    OddEven,
+    Bitonic,
+    OddEvenMerge,
+    Quick,
+    Merge,
+    Heap,
+    Intro,
+    Tim,
+    DropMerge,
+    Pancake,
+    Bogo,
+    /// The network [`crate::network::sort_by`] uses for the length:
+    /// the one of [`crate::network::Network::smallest_available`] up to 32,
+    /// and Batcher's merge exchange, run without building it, above.
+    Network,
+}
+
+/// The parallel sort algorithm a [`par_argsort_by`] runs on the indices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParallelAlgorithm {
+    /// [`crate::parallel::sort_by`]
+    Merge,
+    /// [`crate::parallel::sort_unstable_by`]
+    Sample,
+}
+
+/// Something that can be reordered by [`apply_permutation`]:
+/// a slice, or a tuple of slices that are reordered together.
+pub trait Permute {
+    /// The number of elements, or `None` if the slices of a tuple have different lengths.
+    fn permute_len(&self) -> Option<usize>;
+
+    /// Swaps the elements at the two positions.
+    fn permute_swap(&mut self, i: usize, j: usize);
+}
+
+impl<T> Permute for [T] {
+    fn permute_len(&self) -> Option<usize> {
+        Some(self.len())
+    }
+
+    fn permute_swap(&mut self, i: usize, j: usize) {
+        self.swap(i, j)
+    }
+}
+
+impl<T> Permute for Vec<T> {
+    fn permute_len(&self) -> Option<usize> {
+        Some(self.len())
+    }
+
+    fn permute_swap(&mut self, i: usize, j: usize) {
+        self.swap(i, j)
+    }
+}
+
+impl<S> Permute for &mut S
+where
+    S: Permute + ?Sized,
+{
+    fn permute_len(&self) -> Option<usize> {
+        (**self).permute_len()
+    }
+
+    fn permute_swap(&mut self, i: usize, j: usize) {
+        (**self).permute_swap(i, j)
+    }
+}
+
+macro_rules! impl_permute_for_tuple {
+    ($first:ident $(, $rest:ident)*) => {
+        #[allow(non_snake_case)]
+        impl<$first, $($rest),*> Permute for ($first, $($rest),*)
+        where
+            $first: Permute,
+            $($rest: Permute,)*
+        {
+            fn permute_len(&self) -> Option<usize> {
+                let ($first, $($rest),*) = self;
+                let len = $first.permute_len()?;
+                $(
+                    if $rest.permute_len()? != len {
+                        return None;
+                    }
+                )*
+                Some(len)
+            }
+
+            fn permute_swap(&mut self, i: usize, j: usize) {
+                let ($first, $($rest),*) = self;
+                $first.permute_swap(i, j);
+                $($rest.permute_swap(i, j);)*
+            }
+        }
+    };
+}
+
+impl_permute_for_tuple!(A);
+impl_permute_for_tuple!(A, B);
+impl_permute_for_tuple!(A, B, C);
+impl_permute_for_tuple!(A, B, C, D);
+impl_permute_for_tuple!(A, B, C, D, E);
+impl_permute_for_tuple!(A, B, C, D, E, F);
+
+/// Returns the indices that would sort it in ascending order using the algorithm.
+///
+/// ```rust
+/// use buldak::argsort;
+///
+/// let nums = [30, 10, 20];
+/// assert_eq!(argsort::argsort(argsort::Algorithm::Intro, &nums), vec![1, 2, 0]);
+/// ```
+pub fn argsort<T>(algorithm: Algorithm, array: &[T]) -> Vec<usize>
+where
+    T: std::cmp::Ord,
+{
+    argsort_by(algorithm, array, |l, r| l.cmp(r))
+}
+
+/// It takes a comparator function to determine the order,
+/// and returns the indices that would sort it using the algorithm.
+///
+/// Equal elements are ordered by their indices,
+/// so the result is the same as a stable sort whichever algorithm runs.
+///
+/// ```rust
+/// use buldak::argsort;
+///
+/// let names = ["b", "c", "a", "c"];
+/// let order = argsort::argsort_by(argsort::Algorithm::Quick, &names, |l, r| l.cmp(r).reverse());
+/// assert_eq!(order, vec![1, 3, 0, 2]);
+/// ```
+pub fn argsort_by<T, F>(algorithm: Algorithm, array: &[T], compare: F) -> Vec<usize>
+where
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    let mut indices: Vec<usize> = (0..array.len()).collect();
+    if indices.len() <= 1 {
+        return indices;
+    }
+
+    let compare = &compare;
+    let compare = |l: &usize, r: &usize| compare(&array[*l], &array[*r]).then(l.cmp(r));
+
+    let indices_ = &mut indices[..];
+    match algorithm {
+        Algorithm::Bubble => crate::bubble::sort_by(indices_, compare),
+        Algorithm::SmartBubble => crate::smart_bubble::sort_by(indices_, compare),
+        Algorithm::CocktailShaker => crate::cocktail_shaker::sort_by(indices_, compare),
+        Algorithm::Selection => crate::selection::sort_by(indices_, compare),
+        Algorithm::DoubleSelection => crate::double_selection::sort_by(indices_, compare),
+        Algorithm::Insertion => crate::insertion::sort_by(indices_, compare),
+        Algorithm::BinaryInsertion => crate::binary_insertion::sort_by(indices_, compare),
+        Algorithm::Shell => crate::shell::sort_by(indices_, compare),
+        Algorithm::Stooge => crate::stooge::sort_by(indices_, compare),
+        Algorithm::Gnome => crate::gnome::sort_by(indices_, compare),
+        Algorithm::Comb => crate::comb::sort_by(indices_, compare),
+        Algorithm::Cycle => crate::cycle::sort_by(indices_, compare),
+        Algorithm::OddEven => crate::oddeven::sort_by(indices_, compare),
+        // it never fails
+        Algorithm::Bitonic => crate::bitonic::sort_by(indices_, compare).unwrap(),
+        Algorithm::OddEvenMerge => crate::oddeven_merge::sort_by(indices_, compare),
+        Algorithm::Quick => crate::quick::sort_by(indices_, compare),
+        Algorithm::Merge => crate::merge::sort_by(indices_, compare),
+        Algorithm::Heap => crate::heap::sort_by(indices_, compare),
+        Algorithm::Intro => crate::intro::sort_by(indices_, compare),
+        Algorithm::Tim => crate::tim::sort_by(indices_, compare),
+        Algorithm::DropMerge => crate::drop_merge::sort_by(indices_, compare),
+        Algorithm::Pancake => crate::pancake::sort_by(indices_, compare),
+        Algorithm::Bogo => crate::bogo::sort_by(indices_, compare),
+        Algorithm::Network => {
+            crate::network::_network_sort_impl(indices_, compare, <[usize]>::swap)
+        }
+    }
+
+    indices
+}
+
+/// Returns the indices that would sort it in ascending order using the parallel algorithm.
+///
+/// ```rust
+/// use buldak::argsort;
+///
+/// let nums = [30, 10, 20];
+/// assert_eq!(argsort::par_argsort(argsort::ParallelAlgorithm::Sample, &nums), vec![1, 2, 0]);
+/// ```
+pub fn par_argsort<T>(algorithm: ParallelAlgorithm, array: &[T]) -> Vec<usize>
+where
+    T: std::cmp::Ord + std::marker::Sync,
+{
+    par_argsort_by(algorithm, array, |l, r| l.cmp(r))
+}
+
+/// It takes a comparator function to determine the order,
+/// and returns the indices that would sort it using the parallel algorithm.
+///
+/// Equal elements are ordered by their indices, as in [`argsort_by`].
+///
+/// ```rust
+/// use buldak::argsort;
+///
+/// let names = ["b", "c", "a", "c"];
+/// let order = argsort::par_argsort_by(argsort::ParallelAlgorithm::Merge, &names, |l, r| {
+///     l.cmp(r).reverse()
+/// });
+/// assert_eq!(order, vec![1, 3, 0, 2]);
+/// ```
+pub fn par_argsort_by<T, F>(algorithm: ParallelAlgorithm, array: &[T], compare: F) -> Vec<usize>
+where
+    T: std::marker::Sync,
+    F: Fn(&T, &T) -> std::cmp::Ordering + std::marker::Sync,
+{
+    let mut indices: Vec<usize> = (0..array.len()).collect();
+
+    let compare = &compare;
+    let compare = |l: &usize, r: &usize| compare(&array[*l], &array[*r]).then(l.cmp(r));
+
+    match algorithm {
+        ParallelAlgorithm::Merge => crate::parallel::sort_by(&mut indices, compare),
+        ParallelAlgorithm::Sample => crate::parallel::sort_unstable_by(&mut indices, compare),
+    }
+
+    indices
+}
+
+/// Reorders it so that the element at `i` is the one that was at `permutation[i]`,
+/// following the cycles of the permutation in place.
+///
+/// A tuple of slices is reordered together. It fails without changing anything
+/// if `permutation` is not a permutation of the indices, or if the lengths differ.
+///
+/// ```rust
+/// use buldak::argsort;
+///
+/// let mut ages = vec![30, 10, 20];
+/// let mut names = vec!["c", "a", "b"];
+///
+/// let order = argsort::argsort(argsort::Algorithm::Tim, &ages);
+/// argsort::apply_permutation(&order, &mut (&mut ages, &mut names)).unwrap();
+/// assert_eq!(ages, vec![10, 20, 30]);
+/// assert_eq!(names, vec!["a", "b", "c"]);
+///
+/// assert!(argsort::apply_permutation(&[0, 0, 1], &mut ages).is_err());
+/// ```
+pub fn apply_permutation<S>(permutation: &[usize], target: &mut S) -> Result<(), String>
+where
+    S: Permute + ?Sized,
+{
+    match target.permute_len() {
+        Some(len) if len == permutation.len() => {}
+        Some(len) => {
+            return Err(format!(
+                "The permutation has {} indices but the array has {} elements.",
+                permutation.len(),
+                len
+            ))
+        }
+        None => return Err("The slices have different lengths.".to_string()),
+    }
+    _check_permutation(permutation)?;
+
+    let mut done = vec![false; permutation.len()];
+    for start in 0..permutation.len() {
+        let mut current = start;
+        while !done[current] {
+            done[current] = true;
+
+            let next = permutation[current];
+            if next == start {
+                break;
+            }
+            target.permute_swap(current, next);
+            current = next;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the permutation that undoes it, so that applying both leaves the order unchanged.
+///
+/// ```rust
+/// use buldak::argsort;
+///
+/// let order = vec![2, 0, 1];
+/// let inverse = argsort::invert_permutation(&order).unwrap();
+/// assert_eq!(inverse, vec![1, 2, 0]);
+///
+/// let mut nums = vec!["x", "y", "z"];
+/// argsort::apply_permutation(&order, &mut nums).unwrap();
+/// argsort::apply_permutation(&inverse, &mut nums).unwrap();
+/// assert_eq!(nums, vec!["x", "y", "z"]);
+/// ```
+pub fn invert_permutation(permutation: &[usize]) -> Result<Vec<usize>, String> {
+    _check_permutation(permutation)?;
+
+    let mut inverse = vec![0; permutation.len()];
+    for (i, &p) in permutation.iter().enumerate() {
+        inverse[p] = i;
+    }
+
+    Ok(inverse)
+}
+
+fn _check_permutation(permutation: &[usize]) -> Result<(), String> {
+    let mut seen = vec![false; permutation.len()];
+    for &p in permutation {
+        if p >= permutation.len() || seen[p] {
+            return Err("It's not a permutation of the indices.".to_string());
+        }
+        seen[p] = true;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn argsort() {
+        use super::Algorithm::*;
+
+        let input = vec![5, 1, 4, 2, 2, 0, 5];
+        let mut expected: Vec<usize> = (0..input.len()).collect();
+        expected.sort_by_key(|&i| input[i]);
+
+        let algorithms = [
+            Bubble,
+            SmartBubble,
+            CocktailShaker,
+            Selection,
+            DoubleSelection,
+            Insertion,
+            BinaryInsertion,
+            Shell,
+            Stooge,
+            Gnome,
+            Comb,
+            Cycle,
+            OddEven,
+            Bitonic,
+            OddEvenMerge,
+            Quick,
+            Merge,
+            Heap,
+            Intro,
+            Tim,
+            DropMerge,
+            Pancake,
+            Bogo,
+            Network,
+        ];
+
+        for algorithm in algorithms {
+            assert_eq!(
+                super::argsort(algorithm, &input),
+                expected,
+                "{:?}",
+                algorithm
+            );
+            assert_eq!(super::argsort::<i32>(algorithm, &[]), vec![]);
+        }
+
+        // longer than the networks built for all threads
+        let input: Vec<i64> = (0..40).map(|i| (i * 17) % 11).collect();
+        let mut expected: Vec<usize> = (0..input.len()).collect();
+        expected.sort_by_key(|&i| input[i]);
+        assert_eq!(super::argsort(Network, &input), expected);
+
+        // a comparator that isn't Sync
+        let comparisons = std::cell::Cell::new(0);
+        let actual = super::argsort_by(Intro, &input, |l, r| {
+            comparisons.set(comparisons.get() + 1);
+            l.cmp(r)
+        });
+        assert_eq!(actual, expected);
+        assert!(comparisons.get() > 0);
+    }
+
+    #[test]
+    fn par_argsort() {
+        use super::ParallelAlgorithm::*;
+
+        let input: Vec<i64> = (0..10_000).map(|i| (i * 7919) % 1013).collect();
+        let mut expected: Vec<usize> = (0..input.len()).collect();
+        expected.sort_by_key(|&i| input[i]);
+
+        for algorithm in [Merge, Sample] {
+            assert_eq!(
+                super::par_argsort(algorithm, &input),
+                expected,
+                "{:?}",
+                algorithm
+            );
+            assert_eq!(super::par_argsort::<i32>(algorithm, &[]), vec![]);
+        }
+    }
+
+    #[test]
+    fn apply_permutation() {
+        let keys = vec![3, 1, 2, 0, 4];
+        let order = super::argsort(super::Algorithm::Intro, &keys);
+
+        let mut columns = (
+            keys.clone(),
+            vec!["d", "b", "c", "a", "e"],
+            vec![0.3, 0.1, 0.2, 0.0, 0.4],
+        );
+        super::apply_permutation(&order, &mut columns).unwrap();
+        assert_eq!(columns.0, vec![0, 1, 2, 3, 4]);
+        assert_eq!(columns.1, vec!["a", "b", "c", "d", "e"]);
+        assert_eq!(columns.2, vec![0.0, 0.1, 0.2, 0.3, 0.4]);
+
+        let inverse = super::invert_permutation(&order).unwrap();
+        super::apply_permutation(&inverse, &mut columns.0[..]).unwrap();
+        assert_eq!(columns.0, keys);
+
+        let mut uneven = (vec![1, 2], vec![1]);
+        assert!(super::apply_permutation(&[1, 0], &mut uneven).is_err());
+        assert!(super::invert_permutation(&[1, 2]).is_err());
+    }
+}
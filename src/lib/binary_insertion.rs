@@ -14,7 +14,7 @@
 /// ```
 pub fn sort<T>(array: &mut [T])
 where
-    T: std::cmp::Ord + std::clone::Clone,
+    T: std::cmp::Ord,
 {
     sort_by(array, |l, r| l.cmp(r))
 }
@@ -30,7 +30,7 @@ where
 /// ```
 pub fn sort_reverse<T>(array: &mut [T])
 where
-    T: std::cmp::Ord + std::clone::Clone,
+    T: std::cmp::Ord,
 {
     sort_by(array, |l, r| l.cmp(r).reverse())
 }
@@ -47,53 +47,101 @@ where
 /// ```
 pub fn sort_by<T, F>(array: &mut [T], compare: F)
 where
-    T: std::cmp::Ord + std::clone::Clone,
-    F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
+    F: Fn(&T, &T) -> std::cmp::Ordering,
 {
-    _binary_insertion_sort_impl(array, compare)
+    _binary_insertion_sort_impl(array, &compare)
 }
 
-fn _binary_search<T, F>(array: &[T], item: &T, low: isize, high: isize, compare: F) -> isize
+/// Inserts a batch of elements into a vector that is already sorted in ascending order,
+/// keeping it sorted.
+///
+/// The batch is sorted with a binary insertion sort and merged in.
+/// Elements that are already in the vector stay before the equal elements inserted.
+///
+/// ```rust
+/// use buldak::binary_insertion;
+///
+/// let mut nums = vec![1, 3, 5, 7];
+/// binary_insertion::insert_batch(&mut nums, vec![6, 0, 3]);
+/// assert_eq!(nums, vec![0, 1, 3, 3, 5, 6, 7]);
+/// ```
+pub fn insert_batch<T>(sorted: &mut Vec<T>, batch: Vec<T>)
 where
-    T: std::cmp::Ord + std::clone::Clone,
-    F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
+    T: std::cmp::Ord,
 {
-    if high <= low {
-        return if compare(item, &array[low as usize]) == std::cmp::Ordering::Greater {
-            low + 1
-        } else {
-            low
+    insert_batch_by(sorted, batch, |l, r| l.cmp(r))
+}
+
+/// It takes a comparator function to determine the order,
+/// and inserts a batch of elements into a vector that is already sorted in that order.
+///
+/// ```rust
+/// use buldak::binary_insertion;
+///
+/// let mut scores = vec![(90, "a"), (70, "b")];
+/// binary_insertion::insert_batch_by(&mut scores, vec![(70, "c"), (95, "d")], |l, r| {
+///     l.0.cmp(&r.0).reverse()
+/// });
+/// assert_eq!(scores, vec![(95, "d"), (90, "a"), (70, "b"), (70, "c")]);
+/// ```
+pub fn insert_batch_by<T, F>(sorted: &mut Vec<T>, mut batch: Vec<T>, compare: F)
+where
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    _binary_insertion_sort_impl(&mut batch, &compare);
+
+    let mut merged = Vec::with_capacity(sorted.len() + batch.len());
+    let mut old = std::mem::take(sorted).into_iter().peekable();
+    let mut new = batch.into_iter().peekable();
+
+    loop {
+        let take_old = match (old.peek(), new.peek()) {
+            (Some(l), Some(r)) => compare(l, r) != std::cmp::Ordering::Greater,
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (None, None) => break,
         };
+
+        merged.extend(if take_old { old.next() } else { new.next() });
     }
 
-    let middle = (low + high) / 2;
+    *sorted = merged;
+}
 
-    match compare(item, &array[middle as usize]) {
-        std::cmp::Ordering::Equal => middle + 1,
-        std::cmp::Ordering::Greater => {
-            _binary_search(array, item, middle + 1, high, compare.clone())
+// Returns the position after the last element not greater than the item,
+// searching exponentially back from the end of the sorted array,
+// so that an item that belongs near the end is found in a few comparisons.
+pub(crate) fn _gallop_upper_bound<T, F>(sorted: &[T], item: &T, compare: &F) -> usize
+where
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    let greater = |e: &T| compare(e, item) == std::cmp::Ordering::Greater;
+
+    // everything in sorted[high..] is greater than the item
+    let mut high = sorted.len();
+    let mut step = 1;
+    while high > 0 && greater(&sorted[high - 1]) {
+        let low = high.saturating_sub(step);
+        if !greater(&sorted[low]) {
+            return low + 1 + sorted[low + 1..high - 1].partition_point(|e| !greater(e));
         }
-        std::cmp::Ordering::Less => _binary_search(array, item, low, middle - 1, compare.clone()),
+        high = low;
+        step *= 2;
     }
+
+    high
 }
 
-fn _binary_insertion_sort_impl<T, F>(array: &mut [T], compare: F)
+fn _binary_insertion_sort_impl<T, F>(array: &mut [T], compare: &F)
 where
-    T: std::cmp::Ord + std::clone::Clone,
-    F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
+    F: Fn(&T, &T) -> std::cmp::Ordering,
 {
     for i in 1..array.len() {
-        let mut j = (i - 1) as isize;
+        let (sorted, rest) = array.split_at(i);
+        let index = _gallop_upper_bound(sorted, &rest[0], compare);
 
-        let target = array[i].clone();
-
-        let index = _binary_search(array, &target, 0, j, compare.clone());
-
-        while j >= index {
-            array[(j + 1) as usize] = array[j as usize].clone();
-            j -= 1;
-        }
-        array[(j + 1) as usize] = target;
+        // the item moves in front of the greater elements, which shift up by one
+        array[index..=i].rotate_right(1);
     }
 }
 
@@ -136,4 +184,25 @@ mod tests {
             assert_eq!(actual, case.expected);
         }
     }
+
+    #[test]
+    fn sort_stable() {
+        let input: Vec<(i32, usize)> = (0..200).map(|i| ((i * 37 % 11) as i32, i)).collect();
+
+        let mut expected = input.clone();
+        expected.sort_by_key(|e| e.0);
+        let mut actual = input.clone();
+        super::sort_by(&mut actual, |l, r| l.0.cmp(&r.0));
+        assert_eq!(actual, expected);
+
+        let (sorted, batch) = input.split_at(150);
+        let mut actual = sorted.to_vec();
+        actual.sort_by_key(|e| e.0);
+        super::insert_batch_by(&mut actual, batch.to_vec(), |l, r| l.0.cmp(&r.0));
+        assert_eq!(actual, expected);
+
+        let mut actual = vec![];
+        super::insert_batch(&mut actual, vec![2, 1]);
+        assert_eq!(actual, vec![1, 2]);
+    }
 }
@@ -1,17 +1,20 @@
 //! bitonic sort algorithm.
 //!
-//! This sort works only if the length of the array is 2^N.
+//! Works for any length of the array, without padding.
 //!
-//! **O(Nlog₂N)**
+//! unstable sort  
+//! **O(Nlog²₂N)**
 
 /// Sort in ascending order using a bitonic sort algorithm.
 ///
+/// It never fails. The `Result` is kept for compatibility.
+///
 /// ```rust
 /// use buldak::bitonic;
 ///
-/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21];
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
 /// bitonic::sort(&mut nums);
-/// assert_eq!(nums, [1, 2, 3, 4, 5, 21, 111, 234]);
+/// assert_eq!(nums, [1, 2, 3, 4, 5, 13, 21, 111, 234]);
 /// ```
 pub fn sort<T>(array: &mut [T]) -> Result<(), String>
 where
@@ -22,12 +25,14 @@ where
 
 /// Sort in descending order using a bitonic sort algorithm.
 ///
+/// It never fails. The `Result` is kept for compatibility.
+///
 /// ```rust
 /// use buldak::bitonic;
 ///
-/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21];
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
 /// bitonic::sort_reverse(&mut nums);
-/// assert_eq!(nums, [234, 111, 21, 5, 4, 3, 2, 1]);
+/// assert_eq!(nums, [234, 111, 21, 13, 5, 4, 3, 2, 1]);
 /// ```
 pub fn sort_reverse<T>(array: &mut [T]) -> Result<(), String>
 where
@@ -39,77 +44,296 @@ where
 /// It takes a comparator function to determine the order,
 /// and sorts it using a bitonic sort algorithm.
 ///
+/// It never fails. The `Result` is kept for compatibility.
+///
 /// ```rust
 /// use buldak::bitonic;
 ///
-/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21];
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
 /// bitonic::sort_by(&mut nums, |l, r| l.cmp(r));
-/// assert_eq!(nums, [1, 2, 3, 4, 5, 21, 111, 234]);
+/// assert_eq!(nums, [1, 2, 3, 4, 5, 13, 21, 111, 234]);
 /// ```
 pub fn sort_by<T, F>(array: &mut [T], compare: F) -> Result<(), String>
 where
     T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
 {
-    _bitonic_sort_impl(array, compare)
+    _bitonic_sort_impl(array, compare, utils::swap)
 }
 
-fn _bitonic_sort_impl<T, F>(array: &mut [T], compare: F) -> Result<(), String>
+/// Sort in ascending order using a bitonic sort algorithm,
+/// running the compare-swaps of each stage across threads.
+///
+/// ```rust
+/// use buldak::bitonic;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// bitonic::par_sort(&mut nums);
+/// assert_eq!(nums, [1, 2, 3, 4, 5, 13, 21, 111, 234]);
+/// ```
+pub fn par_sort<T>(array: &mut [T])
 where
-    T: std::cmp::Ord,
-    F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
+    T: std::cmp::Ord + std::marker::Send,
 {
-    let len = array.len() as isize;
+    par_sort_by(array, |l, r| l.cmp(r))
+}
+
+/// Sort in descending order using a bitonic sort algorithm,
+/// running the compare-swaps of each stage across threads.
+///
+/// ```rust
+/// use buldak::bitonic;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// bitonic::par_sort_reverse(&mut nums);
+/// assert_eq!(nums, [234, 111, 21, 13, 5, 4, 3, 2, 1]);
+/// ```
+pub fn par_sort_reverse<T>(array: &mut [T])
+where
+    T: std::cmp::Ord + std::marker::Send,
+{
+    par_sort_by(array, |l, r| l.cmp(r).reverse())
+}
+
+/// It takes a comparator function to determine the order,
+/// and sorts it using a bitonic sort algorithm,
+/// running the compare-swaps of each stage across threads.
+///
+/// The number of threads is taken from `std::thread::available_parallelism`.
+///
+/// ```rust
+/// use buldak::bitonic;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// bitonic::par_sort_by(&mut nums, |l, r| l.cmp(r));
+/// assert_eq!(nums, [1, 2, 3, 4, 5, 13, 21, 111, 234]);
+/// ```
+pub fn par_sort_by<T, F>(array: &mut [T], compare: F)
+where
+    T: std::cmp::Ord + std::marker::Send,
+    F: Fn(&T, &T) -> std::cmp::Ordering + std::marker::Sync,
+{
+    let threads = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    par_sort_by_with_threads(array, threads, compare)
+}
+
+/// It takes a comparator function to determine the order,
+/// and sorts it using a bitonic sort algorithm with at most `threads` threads.
+///
+/// ```rust
+/// use buldak::bitonic;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// bitonic::par_sort_by_with_threads(&mut nums, 3, |l, r| l.cmp(r));
+/// assert_eq!(nums, [1, 2, 3, 4, 5, 13, 21, 111, 234]);
+/// ```
+pub fn par_sort_by_with_threads<T, F>(array: &mut [T], threads: usize, compare: F)
+where
+    T: std::cmp::Ord + std::marker::Send,
+    F: Fn(&T, &T) -> std::cmp::Ordering + std::marker::Sync,
+{
+    _bitonic_par_sort_recursive(array, true, std::cmp::max(threads, 1), &compare)
+}
+
+/// The number of compare-swaps used to sort `len` elements.
+///
+/// ```rust
+/// use buldak::bitonic;
+///
+/// assert_eq!(bitonic::comparator_count(16), 80);
+/// assert_eq!(bitonic::comparator_count(9), 28);
+/// ```
+pub fn comparator_count(len: usize) -> usize {
+    if len <= 1 {
+        return 0;
+    }
 
-    if len != (len & -len) {
-        Err("This sort works only if the length of the array is 2^N.".to_string())
-    } else {
-        _bitonic_sort_recursive(array, 0, array.len(), true, compare);
-        Ok(())
+    let middle = len / 2;
+    comparator_count(middle) + comparator_count(len - middle) + _merge_comparator_count(len)
+}
+
+fn _merge_comparator_count(len: usize) -> usize {
+    if len <= 1 {
+        return 0;
     }
+
+    let middle = _greatest_power_of_two_less_than(len);
+    (len - middle) + _merge_comparator_count(middle) + _merge_comparator_count(len - middle)
 }
 
-fn _bitonic_sort_recursive<T, F>(array: &mut [T], low: usize, count: usize, asc: bool, compare: F)
+pub(crate) fn _bitonic_sort_impl<T, F, S>(
+    array: &mut [T],
+    compare: F,
+    mut swap: S,
+) -> Result<(), String>
 where
-    T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
+    S: FnMut(&mut [T], usize, usize),
+{
+    _bitonic_sort_recursive(array, 0, array.len(), true, compare, &mut swap);
+    Ok(())
+}
+
+// The first half is sorted in the opposite direction of the second,
+// so any length forms a bitonic sequence without padding.
+fn _bitonic_sort_recursive<T, F, S>(
+    array: &mut [T],
+    low: usize,
+    count: usize,
+    asc: bool,
+    compare: F,
+    swap: &mut S,
+) where
+    F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
+    S: FnMut(&mut [T], usize, usize),
 {
     if count > 1 {
         let middle = count / 2;
 
-        _bitonic_sort_recursive(array, low, middle, true, compare.clone());
-        _bitonic_sort_recursive(array, low + middle, middle, false, compare.clone());
+        _bitonic_sort_recursive(array, low, middle, !asc, compare.clone(), swap);
+        _bitonic_sort_recursive(
+            array,
+            low + middle,
+            count - middle,
+            asc,
+            compare.clone(),
+            swap,
+        );
 
-        _bitonic_merge(array, low, count, asc, compare);
+        _bitonic_merge(array, low, count, asc, compare, swap);
     }
 }
 
 mod utils;
 
-fn _bitonic_merge<T, F>(array: &mut [T], low: usize, count: usize, asc: bool, compare: F)
-where
-    T: std::cmp::Ord,
+fn _bitonic_merge<T, F, S>(
+    array: &mut [T],
+    low: usize,
+    count: usize,
+    asc: bool,
+    compare: F,
+    swap: &mut S,
+) where
     F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
+    S: FnMut(&mut [T], usize, usize),
 {
     if count > 1 {
-        let middle = count / 2;
+        let middle = _greatest_power_of_two_less_than(count);
 
-        for i in low..(low + middle) {
-            _compare_swap(array, i, i + middle, asc, compare.clone());
+        for i in low..(low + count - middle) {
+            _compare_swap(array, i, i + middle, asc, compare.clone(), swap);
         }
 
-        _bitonic_merge(array, low, middle, asc, compare.clone());
-        _bitonic_merge(array, low + middle, middle, asc, compare.clone());
+        _bitonic_merge(array, low, middle, asc, compare.clone(), swap);
+        _bitonic_merge(
+            array,
+            low + middle,
+            count - middle,
+            asc,
+            compare.clone(),
+            swap,
+        );
+    }
+}
+
+fn _greatest_power_of_two_less_than(n: usize) -> usize {
+    let mut k = 1;
+    while k * 2 < n {
+        k *= 2;
     }
+    k
 }
 
-fn _compare_swap<T, F>(array: &mut [T], i: usize, j: usize, asc: bool, compare: F)
+/// Below this length, a range is handled on the current thread.
+const PARALLEL_THRESHOLD: usize = 2048;
+
+fn _bitonic_par_sort_recursive<T, F>(array: &mut [T], asc: bool, threads: usize, compare: &F)
+where
+    T: std::cmp::Ord + std::marker::Send,
+    F: Fn(&T, &T) -> std::cmp::Ordering + std::marker::Sync,
+{
+    if threads <= 1 || array.len() <= PARALLEL_THRESHOLD {
+        let len = array.len();
+        _bitonic_sort_recursive(
+            array,
+            0,
+            len,
+            asc,
+            |l: &T, r: &T| compare(l, r),
+            &mut utils::swap,
+        );
+        return;
+    }
+
+    let middle = array.len() / 2;
+    let left_threads = threads / 2;
+
+    {
+        let (left, right) = array.split_at_mut(middle);
+        std::thread::scope(|scope| {
+            scope.spawn(|| _bitonic_par_sort_recursive(left, !asc, left_threads, compare));
+            _bitonic_par_sort_recursive(right, asc, threads - left_threads, compare);
+        });
+    }
+
+    _bitonic_par_merge(array, asc, threads, compare);
+}
+
+fn _bitonic_par_merge<T, F>(array: &mut [T], asc: bool, threads: usize, compare: &F)
+where
+    T: std::cmp::Ord + std::marker::Send,
+    F: Fn(&T, &T) -> std::cmp::Ordering + std::marker::Sync,
+{
+    if threads <= 1 || array.len() <= PARALLEL_THRESHOLD {
+        let len = array.len();
+        _bitonic_merge(
+            array,
+            0,
+            len,
+            asc,
+            |l: &T, r: &T| compare(l, r),
+            &mut utils::swap,
+        );
+        return;
+    }
+
+    let middle = _greatest_power_of_two_less_than(array.len());
+    let (left, right) = array.split_at_mut(middle);
+
+    // every compare-swap of this stage touches left[i] and right[i] only
+    let stage_len = right.len();
+    let chunk = stage_len.div_ceil(threads);
+    std::thread::scope(|scope| {
+        for (l, r) in left[..stage_len]
+            .chunks_mut(chunk)
+            .zip(right.chunks_mut(chunk))
+        {
+            scope.spawn(move || {
+                for (x, y) in l.iter_mut().zip(r.iter_mut()) {
+                    if asc == (compare(x, y) == std::cmp::Ordering::Greater) {
+                        std::mem::swap(x, y);
+                    }
+                }
+            });
+        }
+    });
+
+    let left_threads = threads / 2;
+    std::thread::scope(|scope| {
+        scope.spawn(|| _bitonic_par_merge(left, asc, left_threads, compare));
+        _bitonic_par_merge(right, asc, threads - left_threads, compare);
+    });
+}
+
+fn _compare_swap<T, F, S>(array: &mut [T], i: usize, j: usize, asc: bool, compare: F, swap: &mut S)
 where
-    T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
+    S: FnMut(&mut [T], usize, usize),
 {
     if asc == (compare(&array[i], &array[j]) == std::cmp::Ordering::Greater) {
-        utils::swap(array, i, j);
+        swap(array, i, j);
     }
 }
 
@@ -122,10 +346,16 @@ mod tests {
             expected: Vec<i32>,
         }
 
-        let test_cases = vec![TestCase {
-            input: vec![1, 4, 2, 3, 5, 111, 234, 21],
-            expected: vec![1, 2, 3, 4, 5, 21, 111, 234],
-        }];
+        let test_cases = vec![
+            TestCase {
+                input: vec![1, 4, 2, 3, 5, 111, 234, 21],
+                expected: vec![1, 2, 3, 4, 5, 21, 111, 234],
+            },
+            TestCase {
+                input: vec![1, 4, 2, 3, 5, 111, 234, 21, 13],
+                expected: vec![1, 2, 3, 4, 5, 13, 21, 111, 234],
+            },
+        ];
 
         for case in test_cases {
             let mut actual = case.input.clone();
@@ -152,4 +382,18 @@ mod tests {
             assert_eq!(actual, case.expected);
         }
     }
+
+    #[test]
+    fn par_sort_ascending() {
+        let input: Vec<i32> = (0..10_000).map(|i| (i * 7919) % 1013).collect();
+
+        let mut expected = input.clone();
+        expected.sort();
+
+        for threads in [1, 2, 3, 8] {
+            let mut actual = input.clone();
+            super::par_sort_by_with_threads(&mut actual, threads, |l, r| l.cmp(r));
+            assert_eq!(actual, expected);
+        }
+    }
 }
@@ -3,6 +3,8 @@
 //! unstable sort  
 //! **best:O(1), worst:O(∞)**
 
+mod utils;
+
 /// Sort in ascending order using a bogo sort algorithm.
 ///
 /// ```rust
@@ -50,16 +52,16 @@ where
     T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering,
 {
-    _bogo_sort_impl(array, compare)
+    _bogo_sort_impl(array, compare, utils::swap)
 }
 
-fn _bogo_sort_impl<T, F>(array: &mut [T], compare: F)
+pub(crate) fn _bogo_sort_impl<T, F, S>(array: &mut [T], compare: F, mut swap: S)
 where
-    T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering,
+    S: FnMut(&mut [T], usize, usize),
 {
     while _sorted(array, &compare) == false {
-        _shuffle(array);
+        _shuffle(array, &mut swap);
     }
 }
 
@@ -81,11 +83,18 @@ where
     return true;
 }
 
-use rand::seq::SliceRandom;
+use rand::Rng;
 
-fn _shuffle<T>(array: &mut [T]) {
+// Fisher-Yates, the same shuffle as SliceRandom::shuffle, one swap at a time.
+fn _shuffle<T, S>(array: &mut [T], swap: &mut S)
+where
+    S: FnMut(&mut [T], usize, usize),
+{
     let mut rng = rand::thread_rng();
-    array.shuffle(&mut rng);
+    for i in (1..array.len()).rev() {
+        let j = rng.gen_range(0, i + 1);
+        swap(array, i, j);
+    }
 }
 
 #[cfg(test)]
@@ -55,13 +55,13 @@ where
     T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering,
 {
-    _bubble_sort_impl(array, compare)
+    _bubble_sort_impl(array, compare, utils::swap)
 }
 
-fn _bubble_sort_impl<T, F>(array: &mut [T], compare: F)
+pub(crate) fn _bubble_sort_impl<T, F, S>(array: &mut [T], compare: F, mut swap: S)
 where
-    T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering,
+    S: FnMut(&mut [T], usize, usize),
 {
     let mut last = array.len();
 
@@ -71,7 +71,7 @@ where
         while (i + 1) < last {
             match compare(&array[i], &array[i + 1]) {
                 std::cmp::Ordering::Less => (),
-                std::cmp::Ordering::Greater => utils::swap(array, i, i + 1),
+                std::cmp::Ordering::Greater => swap(array, i, i + 1),
                 std::cmp::Ordering::Equal => (),
             }
             i += 1;
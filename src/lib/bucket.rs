@@ -0,0 +1,390 @@
+//! bucket sort algorithm.
+//!
+//! Distributes the elements into buckets by their value, sorts every bucket, and concatenates them.
+//!
+//! stable sort (with `Algorithm::Insertion` or `Algorithm::Tim`)
+//! **O(N)**: for uniformly distributed values
+
+/// The algorithm used to sort each bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    /// [`crate::insertion`], best for small buckets.
+    Insertion,
+    /// [`crate::tim`], stable and good for partly sorted buckets.
+    Tim,
+    /// [`crate::intro`], unstable but never quadratic.
+    Intro,
+}
+
+/// Where NaNs go when sorting floats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NanPolicy {
+    /// Before every number, in their original order.
+    First,
+    /// After every number, in their original order.
+    Last,
+    /// Fail without changing the array.
+    Error,
+}
+
+/// A float type that can be sorted with [`sort`].
+pub trait Float: std::marker::Copy + std::cmp::PartialOrd {
+    /// The value as a `f64`, used to find its bucket.
+    fn to_f64(self) -> f64;
+}
+
+impl Float for f32 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Float for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+}
+
+/// Sort in ascending order using a bucket sort algorithm,
+/// with one bucket per element and an insertion sort in each bucket.
+///
+/// ```rust
+/// use buldak::bucket;
+///
+/// let mut nums = [0.42, 0.32, 0.23, f64::NAN, 0.52, 0.25, 0.47, 0.51];
+/// bucket::sort(&mut nums, bucket::NanPolicy::Last).unwrap();
+/// assert_eq!(nums[..7], [0.23, 0.25, 0.32, 0.42, 0.47, 0.51, 0.52]);
+/// assert!(nums[7].is_nan());
+///
+/// assert!(bucket::sort(&mut nums, bucket::NanPolicy::Error).is_err());
+/// ```
+pub fn sort<T>(array: &mut [T], nan: NanPolicy) -> Result<(), String>
+where
+    T: Float,
+{
+    sort_with(array, array.len(), Algorithm::Insertion, nan)
+}
+
+/// Sort in descending order using a bucket sort algorithm,
+/// with one bucket per element and an insertion sort in each bucket.
+///
+/// ```rust
+/// use buldak::bucket;
+///
+/// let mut nums = [0.42f32, 0.32, 0.23, f32::NAN, 0.52, 0.25];
+/// bucket::sort_reverse(&mut nums, bucket::NanPolicy::First).unwrap();
+/// assert!(nums[0].is_nan());
+/// assert_eq!(nums[1..], [0.52, 0.42, 0.32, 0.25, 0.23]);
+/// ```
+pub fn sort_reverse<T>(array: &mut [T], nan: NanPolicy) -> Result<(), String>
+where
+    T: Float,
+{
+    sort_reverse_with(array, array.len(), Algorithm::Insertion, nan)
+}
+
+/// Sort in ascending order using a bucket sort algorithm
+/// with `buckets` buckets, each sorted by `algorithm`.
+///
+/// ```rust
+/// use buldak::bucket;
+///
+/// let mut nums = [3.5, -1.0, f64::INFINITY, 2.25, -7.5, 0.0];
+/// bucket::sort_with(&mut nums, 4, bucket::Algorithm::Tim, bucket::NanPolicy::Error).unwrap();
+/// assert_eq!(nums, [-7.5, -1.0, 0.0, 2.25, 3.5, f64::INFINITY]);
+/// ```
+pub fn sort_with<T>(
+    array: &mut [T],
+    buckets: usize,
+    algorithm: Algorithm,
+    nan: NanPolicy,
+) -> Result<(), String>
+where
+    T: Float,
+{
+    _float_bucket_sort_impl(array, buckets, algorithm, nan, true)
+}
+
+/// Sort in descending order using a bucket sort algorithm
+/// with `buckets` buckets, each sorted by `algorithm`.
+///
+/// ```rust
+/// use buldak::bucket;
+///
+/// let mut nums = [3.5, -1.0, f64::INFINITY, 2.25, -7.5, 0.0];
+/// bucket::sort_reverse_with(&mut nums, 4, bucket::Algorithm::Intro, bucket::NanPolicy::Error)
+///     .unwrap();
+/// assert_eq!(nums, [f64::INFINITY, 3.5, 2.25, 0.0, -1.0, -7.5]);
+/// ```
+pub fn sort_reverse_with<T>(
+    array: &mut [T],
+    buckets: usize,
+    algorithm: Algorithm,
+    nan: NanPolicy,
+) -> Result<(), String>
+where
+    T: Float,
+{
+    _float_bucket_sort_impl(array, buckets, algorithm, nan, false)
+}
+
+/// It takes a function that maps each element to one of `buckets` buckets,
+/// and a comparator function to determine the order within a bucket,
+/// and sorts it using a bucket sort algorithm with `algorithm` in each bucket.
+///
+/// The mapping must keep the order: an element that comes before another
+/// can't be in a later bucket. It fails if a bucket index is not below `buckets`.
+///
+/// ```rust
+/// use buldak::bucket;
+///
+/// let mut scores = [("kim", 87), ("lee", 42), ("park", 99), ("choi", 80)];
+/// bucket::sort_by_bucket(
+///     &mut scores,
+///     10,
+///     |e| e.1 / 10,
+///     bucket::Algorithm::Insertion,
+///     |l, r| l.1.cmp(&r.1),
+/// )
+/// .unwrap();
+/// assert_eq!(scores, [("lee", 42), ("choi", 80), ("kim", 87), ("park", 99)]);
+/// ```
+pub fn sort_by_bucket<T, B, F>(
+    array: &mut [T],
+    buckets: usize,
+    bucket_of: B,
+    algorithm: Algorithm,
+    compare: F,
+) -> Result<(), String>
+where
+    T: std::clone::Clone,
+    B: Fn(&T) -> usize,
+    F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
+{
+    _bucket_sort_impl(array, buckets, bucket_of, algorithm, compare)
+}
+
+fn _bucket_sort_impl<T, B, F>(
+    array: &mut [T],
+    buckets: usize,
+    bucket_of: B,
+    algorithm: Algorithm,
+    compare: F,
+) -> Result<(), String>
+where
+    T: std::clone::Clone,
+    B: Fn(&T) -> usize,
+    F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
+{
+    if array.is_empty() {
+        return Ok(());
+    }
+    if buckets == 0 {
+        return Err("There must be at least one bucket.".to_string());
+    }
+
+    let mut bins: Vec<Vec<T>> = (0..buckets).map(|_| vec![]).collect();
+
+    for e in array.iter() {
+        let index = bucket_of(e);
+        if index >= buckets {
+            return Err("The bucket index is out of range.".to_string());
+        }
+        bins[index].push(e.clone());
+    }
+
+    let mut i = 0;
+    for mut bin in bins {
+        match algorithm {
+            Algorithm::Insertion => crate::insertion::sort_by(&mut bin, compare.clone()),
+            Algorithm::Tim => crate::tim::sort_by(&mut bin, compare.clone()),
+            Algorithm::Intro => crate::intro::sort_by(&mut bin, compare.clone()),
+        }
+
+        for e in bin {
+            array[i] = e;
+            i += 1;
+        }
+    }
+
+    Ok(())
+}
+
+// NaNs are set aside first, the numbers are then spread linearly between the finite min and max.
+fn _float_bucket_sort_impl<T>(
+    array: &mut [T],
+    buckets: usize,
+    algorithm: Algorithm,
+    nan: NanPolicy,
+    asc: bool,
+) -> Result<(), String>
+where
+    T: Float,
+{
+    // checked before the NaNs are moved, so nothing changes when it fails
+    if buckets == 0 && !array.is_empty() {
+        return Err("There must be at least one bucket.".to_string());
+    }
+
+    let is_nan = |e: &T| e.to_f64().is_nan();
+
+    let nan_count = array.iter().filter(|e| is_nan(e)).count();
+    if nan_count > 0 && nan == NanPolicy::Error {
+        return Err("The array contains NaN.".to_string());
+    }
+
+    let numbers = if nan_count == 0 {
+        array
+    } else {
+        let nans = array.iter().filter(|e| is_nan(e));
+        let others = array.iter().filter(|e| !is_nan(e));
+        let partitioned: Vec<T> = if nan == NanPolicy::First {
+            nans.chain(others).copied().collect()
+        } else {
+            others.chain(nans).copied().collect()
+        };
+        array.copy_from_slice(&partitioned);
+
+        let len = array.len();
+        if nan == NanPolicy::First {
+            &mut array[nan_count..]
+        } else {
+            &mut array[..(len - nan_count)]
+        }
+    };
+
+    let finite = numbers.iter().map(|e| e.to_f64()).filter(|e| e.is_finite());
+    let (min, max) = finite.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), e| {
+        (min.min(e), max.max(e))
+    });
+
+    let last = buckets.saturating_sub(1);
+    let bucket_of = |e: &T| {
+        let e = e.to_f64();
+        let index = if e == f64::NEG_INFINITY {
+            0
+        } else if e == f64::INFINITY {
+            last
+        } else if max > min {
+            // halved so that max - min can't overflow
+            let ratio = (e / 2.0 - min / 2.0) / (max / 2.0 - min / 2.0);
+            std::cmp::min((ratio * last as f64) as usize, last)
+        } else {
+            0
+        };
+
+        if asc {
+            index
+        } else {
+            last - index
+        }
+    };
+
+    if asc {
+        _bucket_sort_impl(numbers, buckets, bucket_of, algorithm, |l: &T, r: &T| {
+            l.partial_cmp(r).unwrap()
+        })
+    } else {
+        _bucket_sort_impl(numbers, buckets, bucket_of, algorithm, |l: &T, r: &T| {
+            r.partial_cmp(l).unwrap()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn sort_ascending() {
+        struct TestCase {
+            input: Vec<f64>,
+            expected: Vec<f64>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                input: vec![1.0, 4.0, 2.0, 3.0, 5.0, 111.0, 234.0, 21.0, 13.0],
+                expected: vec![1.0, 2.0, 3.0, 4.0, 5.0, 13.0, 21.0, 111.0, 234.0],
+            },
+            TestCase {
+                input: vec![-f64::MAX, f64::MAX, 0.5, f64::NEG_INFINITY, -0.5],
+                expected: vec![f64::NEG_INFINITY, -f64::MAX, -0.5, 0.5, f64::MAX],
+            },
+            TestCase {
+                input: vec![],
+                expected: vec![],
+            },
+        ];
+
+        for case in test_cases {
+            for algorithm in [
+                super::Algorithm::Insertion,
+                super::Algorithm::Tim,
+                super::Algorithm::Intro,
+            ] {
+                let mut actual = case.input.clone();
+                super::sort_with(&mut actual, 3, algorithm, super::NanPolicy::Error).unwrap();
+                assert_eq!(actual, case.expected);
+            }
+        }
+    }
+
+    #[test]
+    fn sort_descending() {
+        struct TestCase {
+            input: Vec<f64>,
+            expected: Vec<f64>,
+        }
+
+        let test_cases = vec![TestCase {
+            input: vec![1.0, 4.0, 2.0, 3.0, 5.0, 111.0, 234.0, 21.0, 13.0],
+            expected: vec![234.0, 111.0, 21.0, 13.0, 5.0, 4.0, 3.0, 2.0, 1.0],
+        }];
+
+        for case in test_cases {
+            let mut actual = case.input.clone();
+            super::sort_reverse(&mut actual, super::NanPolicy::Error).unwrap();
+            assert_eq!(actual, case.expected);
+        }
+    }
+
+    #[test]
+    fn sort_by_bucket() {
+        let input: Vec<(u32, usize)> = (0..1000).map(|i| ((i * 7919) % 1000, i as usize)).collect();
+
+        let mut expected = input.clone();
+        expected.sort_by_key(|e| e.0);
+
+        let mut actual = input.clone();
+        super::sort_by_bucket(
+            &mut actual,
+            100,
+            |e| e.0 as usize / 10,
+            super::Algorithm::Tim,
+            |l, r| l.0.cmp(&r.0),
+        )
+        .unwrap();
+        assert_eq!(actual, expected);
+
+        let mut actual = input.clone();
+        let result = super::sort_by_bucket(
+            &mut actual,
+            10,
+            |e| e.0 as usize / 10,
+            super::Algorithm::Tim,
+            |l, r| l.0.cmp(&r.0),
+        );
+        assert!(result.is_err());
+        assert_eq!(actual, input);
+    }
+
+    #[test]
+    fn sort_errors() {
+        let input = vec![2.0, f64::NAN, 1.0];
+
+        for nan in [super::NanPolicy::First, super::NanPolicy::Last] {
+            let mut actual = input.clone();
+            assert!(super::sort_with(&mut actual, 0, super::Algorithm::Tim, nan).is_err());
+            assert!(actual[0] == 2.0 && actual[1].is_nan() && actual[2] == 1.0);
+        }
+    }
+}
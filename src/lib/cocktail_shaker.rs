@@ -51,13 +51,13 @@ where
     T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering,
 {
-    _cocktail_shaker_sort_impl(array, compare)
+    _cocktail_shaker_sort_impl(array, compare, utils::swap)
 }
 
-fn _cocktail_shaker_sort_impl<T, F>(array: &mut [T], compare: F)
+pub(crate) fn _cocktail_shaker_sort_impl<T, F, S>(array: &mut [T], compare: F, mut swap: S)
 where
-    T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering,
+    S: FnMut(&mut [T], usize, usize),
 {
     let mut first = 0;
     let mut last = array.len();
@@ -70,7 +70,7 @@ where
             match compare(&array[i - 1], &array[i]) {
                 std::cmp::Ordering::Less => (),
                 std::cmp::Ordering::Greater => {
-                    utils::swap(array, i - 1, i);
+                    swap(array, i - 1, i);
                     shift = i;
                 }
                 std::cmp::Ordering::Equal => (),
@@ -84,7 +84,7 @@ where
             match compare(&array[i - 1], &array[i]) {
                 std::cmp::Ordering::Less => (),
                 std::cmp::Ordering::Greater => {
-                    utils::swap(array, i - 1, i);
+                    swap(array, i - 1, i);
                     shift = i;
                 }
                 std::cmp::Ordering::Equal => (),
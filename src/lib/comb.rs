@@ -51,13 +51,13 @@ where
     T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering,
 {
-    _comb_sort_impl(array, compare);
+    _comb_sort_impl(array, compare, utils::swap);
 }
 
-fn _comb_sort_impl<T, F>(array: &mut [T], compare: F)
+pub(crate) fn _comb_sort_impl<T, F, S>(array: &mut [T], compare: F, mut swap: S)
 where
-    T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering,
+    S: FnMut(&mut [T], usize, usize),
 {
     let mut gap = array.len();
     let shrink = 1.3_f64;
@@ -76,7 +76,7 @@ where
         let mut i = 0;
         while i + gap < array.len() {
             if compare(&array[i], &array[i + gap]) == std::cmp::Ordering::Greater {
-                utils::swap(array, i, i + gap);
+                swap(array, i, i + gap);
                 sorted = false;
             }
             i += 1;
@@ -1,7 +1,11 @@
 //! counting sort algorithm.
 //!
-//! unstable sort  
-//! **O(N)**
+//! [`sort`] and [`sort_reverse`] rebuild the values from their counts.
+//! [`sort_by_key_in_range`] and the other `sort_by_key_in_range*` functions move whole elements by their keys.
+//!
+//! stable sort: `sort_by_key_in_range*`  
+//! unstable sort: `sort`, `sort_reverse`  
+//! **O(N+R)**: R=the size of the range of the keys
 
 /// Sort in ascending order using a counting sort algorithm.
 ///
@@ -21,17 +25,6 @@ where
     _counting_sort_max_scan(array, true, true)
 }
 
-// pub fn sort<T, Max>(array: &mut [T], max: Max, signed: bool) -> Result<(), String>
-// where
-//     T: std::convert::TryInto<isize> + std::convert::TryFrom<isize> + std::clone::Clone,
-//     <T as std::convert::TryInto<isize>>::Error: std::fmt::Debug,
-//     <T as std::convert::TryFrom<isize>>::Error: std::fmt::Debug,
-//     Max: std::convert::TryInto<isize>,
-//     <Max as std::convert::TryInto<isize>>::Error: std::fmt::Debug,
-// {
-//     _counting_impl(array, max, true, signed)
-// }
-
 /// Sort in descending order using a counting algorithm.
 ///
 /// ```rust
@@ -50,16 +43,148 @@ where
     _counting_sort_max_scan(array, false, true)
 }
 
-// pub fn sort_reverse<T, Max>(array: &mut [T], max: Max, signed: bool) -> Result<(), String>
-// where
-//     T: std::convert::TryInto<isize> + std::convert::TryFrom<isize> + std::clone::Clone,
-//     <T as std::convert::TryInto<isize>>::Error: std::fmt::Debug,
-//     <T as std::convert::TryFrom<isize>>::Error: std::fmt::Debug,
-//     Max: std::convert::TryInto<isize>,
-//     <Max as std::convert::TryInto<isize>>::Error: std::fmt::Debug,
-// {
-//     _counting_impl(array, max, false, signed)
-// }
+/// The default limit on the memory used for the counts of [`sort_by_key_in_range`], in bytes.
+pub const DEFAULT_MEMORY_BUDGET: usize = 1 << 28;
+
+/// The buffers used by [`sort_by_key_in_range_with_buffers`].
+///
+/// Keeping them between calls saves the allocations when sorting many arrays.
+pub struct Buffers<T> {
+    counts: Vec<usize>,
+    scratch: Vec<T>,
+    memory_budget: usize,
+}
+
+impl<T> Buffers<T> {
+    /// Empty buffers with the [`DEFAULT_MEMORY_BUDGET`].
+    pub fn new() -> Self {
+        Self::with_memory_budget(DEFAULT_MEMORY_BUDGET)
+    }
+
+    /// Empty buffers that refuse key ranges needing more than `memory_budget` bytes of counts.
+    pub fn with_memory_budget(memory_budget: usize) -> Self {
+        Buffers {
+            counts: vec![],
+            scratch: vec![],
+            memory_budget,
+        }
+    }
+
+    /// The limit on the memory used for the counts, in bytes.
+    pub fn memory_budget(&self) -> usize {
+        self.memory_budget
+    }
+}
+
+impl<T> std::default::Default for Buffers<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// It takes a function that extracts a key within `range` from each element,
+/// and sorts it in ascending order of the keys using a stable counting sort algorithm.
+///
+/// It fails if a key is out of `range`, or if counting the range needs more than [`DEFAULT_MEMORY_BUDGET`].
+///
+/// ```rust
+/// use buldak::counting;
+///
+/// let mut years = [(2003, "a"), (1999, "b"), (2003, "c"), (2001, "d")];
+/// counting::sort_by_key_in_range(&mut years, 1990..=2010, |e| e.0).unwrap();
+/// assert_eq!(years, [(1999, "b"), (2001, "d"), (2003, "a"), (2003, "c")]);
+///
+/// assert!(counting::sort_by_key_in_range(&mut years, 2000..=2010, |e| e.0).is_err());
+/// ```
+pub fn sort_by_key_in_range<T, F>(
+    array: &mut [T],
+    range: std::ops::RangeInclusive<isize>,
+    key: F,
+) -> Result<(), String>
+where
+    T: std::clone::Clone,
+    F: Fn(&T) -> isize,
+{
+    _counting_sort_by_key_impl(array, range, key, &mut Buffers::new(), true)
+}
+
+/// It takes a function that extracts a key within `range` from each element,
+/// and sorts it in descending order of the keys using a stable counting sort algorithm.
+///
+/// ```rust
+/// use buldak::counting;
+///
+/// let mut years = [(2003, "a"), (1999, "b"), (2003, "c"), (2001, "d")];
+/// counting::sort_by_key_in_range_reverse(&mut years, 1990..=2010, |e| e.0).unwrap();
+/// assert_eq!(years, [(2003, "a"), (2003, "c"), (2001, "d"), (1999, "b")]);
+/// ```
+pub fn sort_by_key_in_range_reverse<T, F>(
+    array: &mut [T],
+    range: std::ops::RangeInclusive<isize>,
+    key: F,
+) -> Result<(), String>
+where
+    T: std::clone::Clone,
+    F: Fn(&T) -> isize,
+{
+    _counting_sort_by_key_impl(array, range, key, &mut Buffers::new(), false)
+}
+
+/// Same as [`sort_by_key_in_range`], but reuses the given buffers and their memory budget.
+///
+/// ```rust
+/// use buldak::counting;
+///
+/// let mut buffers = counting::Buffers::new();
+///
+/// let mut first = [3, -2, 7];
+/// counting::sort_by_key_in_range_with_buffers(&mut first, -5..=10, |e| *e, &mut buffers).unwrap();
+/// assert_eq!(first, [-2, 3, 7]);
+///
+/// let mut second = [9, 0, -5];
+/// counting::sort_by_key_in_range_with_buffers(&mut second, -5..=10, |e| *e, &mut buffers).unwrap();
+/// assert_eq!(second, [-5, 0, 9]);
+///
+/// let mut small = counting::Buffers::with_memory_budget(64);
+/// assert!(counting::sort_by_key_in_range_with_buffers(&mut second, 0..=1000, |e| *e, &mut small).is_err());
+/// ```
+pub fn sort_by_key_in_range_with_buffers<T, F>(
+    array: &mut [T],
+    range: std::ops::RangeInclusive<isize>,
+    key: F,
+    buffers: &mut Buffers<T>,
+) -> Result<(), String>
+where
+    T: std::clone::Clone,
+    F: Fn(&T) -> isize,
+{
+    _counting_sort_by_key_impl(array, range, key, buffers, true)
+}
+
+/// Same as [`sort_by_key_in_range_reverse`], but reuses the given buffers and their memory budget.
+///
+/// ```rust
+/// use buldak::counting;
+///
+/// let mut buffers = counting::Buffers::new();
+///
+/// let mut nums = [3, -2, 7];
+/// counting::sort_by_key_in_range_reverse_with_buffers(&mut nums, -5..=10, |e| *e, &mut buffers)
+///     .unwrap();
+/// assert_eq!(nums, [7, 3, -2]);
+/// ```
+pub fn sort_by_key_in_range_reverse_with_buffers<T, F>(
+    array: &mut [T],
+    range: std::ops::RangeInclusive<isize>,
+    key: F,
+    buffers: &mut Buffers<T>,
+) -> Result<(), String>
+where
+    T: std::clone::Clone,
+    F: Fn(&T) -> isize,
+{
+    _counting_sort_by_key_impl(array, range, key, buffers, false)
+}
 
 fn _counting_sort_impl<T, Max>(
     array: &mut [T],
@@ -180,6 +305,75 @@ where
     return _counting_sort_impl(array, abs_max, asc, signed);
 }
 
+// Keys are counted first, so nothing is moved when one of them is out of the range.
+fn _counting_sort_by_key_impl<T, F>(
+    array: &mut [T],
+    range: std::ops::RangeInclusive<isize>,
+    key: F,
+    buffers: &mut Buffers<T>,
+    asc: bool,
+) -> Result<(), String>
+where
+    T: std::clone::Clone,
+    F: Fn(&T) -> isize,
+{
+    if array.is_empty() {
+        return Ok(());
+    }
+
+    let (min, max) = range.into_inner();
+    if min > max {
+        return Err("The range is empty.".to_string());
+    }
+
+    let span = (max as i128 - min as i128 + 1) as u128;
+    if span.saturating_mul(std::mem::size_of::<usize>() as u128) > buffers.memory_budget as u128 {
+        return Err("The range needs more memory than the memory budget.".to_string());
+    }
+
+    let counts = &mut buffers.counts;
+    counts.clear();
+    counts.resize(span as usize, 0);
+
+    for e in array.iter() {
+        let k = key(e);
+        if k < min || k > max {
+            return Err("The key is out of the range.".to_string());
+        }
+        counts[(k as i128 - min as i128) as usize] += 1;
+    }
+
+    // turn the counts into the first position of each key
+    let mut total = 0;
+    if asc {
+        for count in counts.iter_mut() {
+            let c = *count;
+            *count = total;
+            total += c;
+        }
+    } else {
+        for count in counts.iter_mut().rev() {
+            let c = *count;
+            *count = total;
+            total += c;
+        }
+    }
+
+    let scratch = &mut buffers.scratch;
+    scratch.clear();
+    scratch.extend_from_slice(array);
+
+    for e in scratch.iter() {
+        let index = (key(e) as i128 - min as i128) as usize;
+        array[counts[index]] = e.clone();
+        counts[index] += 1;
+    }
+
+    scratch.clear();
+
+    Ok(())
+}
+
 mod tests {
     #[test]
     fn sort_ascending() {
@@ -218,4 +412,35 @@ mod tests {
             assert_eq!(actual, case.expected);
         }
     }
+
+    #[test]
+    fn sort_by_key_in_range() {
+        let input: Vec<(i64, usize)> = (0..1000)
+            .map(|i| (1_000_000 + (i * 37) % 50, i as usize))
+            .collect();
+
+        let mut expected = input.clone();
+        expected.sort_by_key(|e| e.0);
+        let mut actual = input.clone();
+        super::sort_by_key_in_range(&mut actual, 1_000_000..=1_000_049, |e| e.0 as isize).unwrap();
+        assert_eq!(actual, expected);
+
+        expected.sort_by_key(|e| std::cmp::Reverse(e.0));
+        let mut actual = input.clone();
+        super::sort_by_key_in_range_reverse(&mut actual, 999_990..=1_000_100, |e| e.0 as isize)
+            .unwrap();
+        assert_eq!(actual, expected);
+
+        let mut actual = input.clone();
+        assert!(
+            super::sort_by_key_in_range(&mut actual, 1_000_000..=1_000_048, |e| e.0 as isize)
+                .is_err()
+        );
+        assert_eq!(actual, input);
+
+        assert!(
+            super::sort_by_key_in_range(&mut actual, isize::MIN..=isize::MAX, |e| e.0 as isize)
+                .is_err()
+        );
+    }
 }
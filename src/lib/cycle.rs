@@ -47,22 +47,188 @@ where
 /// ```
 pub fn sort_by<T, F>(array: &mut [T], compare: F)
 where
-    T: std::cmp::Ord + std::clone::Clone,
+    T: std::clone::Clone,
     F: Fn(&T, &T) -> std::cmp::Ordering,
 {
-    _cycle_sort_impl(array, compare)
+    _cycle_sort_impl(array, compare, |_, _| {});
 }
 
-fn _cycle_sort_impl<T, F>(array: &mut [T], compare: F)
+/// Sort in ascending order using a cycle sort algorithm,
+/// and returns how many elements were written.
+///
+/// Every element is written at most once, and only if it's out of place.
+///
+/// ```rust
+/// use buldak::cycle;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// let writes = cycle::sort_counting_writes(&mut nums);
+/// assert_eq!(nums, [1, 2, 3, 4, 5, 13, 21, 111, 234]);
+/// assert_eq!(writes, 7);
+/// ```
+pub fn sort_counting_writes<T>(array: &mut [T]) -> usize
 where
     T: std::cmp::Ord + std::clone::Clone,
+{
+    sort_by_counting_writes(array, |l, r| l.cmp(r))
+}
+
+/// It takes a comparator function to determine the order,
+/// sorts it using a cycle sort algorithm, and returns how many elements were written.
+///
+/// ```rust
+/// use buldak::cycle;
+///
+/// let mut nums = [3, 2, 1];
+/// let writes = cycle::sort_by_counting_writes(&mut nums, |l, r| l.cmp(r).reverse());
+/// assert_eq!(nums, [3, 2, 1]);
+/// assert_eq!(writes, 0);
+/// ```
+pub fn sort_by_counting_writes<T, F>(array: &mut [T], compare: F) -> usize
+where
+    T: std::clone::Clone,
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    _cycle_sort_impl(array, compare, |_, _| {})
+}
+
+/// It takes a comparator function to determine the order,
+/// sorts it using a cycle sort algorithm,
+/// and calls `write` with the position and the new value of every element written.
+///
+/// The array is read as a copy of the storage, so `write` can mirror the writes
+/// to storage where writing is expensive, such as flash memory. It returns how many elements were written.
+///
+/// ```rust
+/// use buldak::cycle;
+///
+/// let mut storage = vec![30, 10, 20];
+/// let mut nums = storage.clone();
+/// let writes = cycle::sort_by_with_writes(&mut nums, |l, r| l.cmp(r), |index, value| {
+///     storage[index] = *value;
+/// });
+/// assert_eq!(storage, [10, 20, 30]);
+/// assert_eq!(writes, 3);
+/// ```
+pub fn sort_by_with_writes<T, F, W>(array: &mut [T], compare: F, write: W) -> usize
+where
+    T: std::clone::Clone,
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+    W: FnMut(usize, &T),
+{
+    _cycle_sort_impl(array, compare, write)
+}
+
+/// Sort in ascending order an array of all the integers in a range, each exactly once,
+/// and returns how many elements were written.
+///
+/// The position of every element is its value minus the min,
+/// so it takes **O(N)** instead of searching for the positions.
+/// It fails without changing the array if the elements are not such a permutation.
+///
+/// ```rust
+/// use buldak::cycle;
+///
+/// let mut nums = [103, 100, 102, 101];
+/// assert_eq!(cycle::sort_permutation(&mut nums), Ok(3));
+/// assert_eq!(nums, [100, 101, 102, 103]);
+///
+/// assert!(cycle::sort_permutation(&mut [1, 3, 4]).is_err());
+/// ```
+pub fn sort_permutation<T>(array: &mut [T]) -> Result<usize, String>
+where
+    T: std::convert::TryInto<isize> + std::clone::Clone,
+{
+    _permutation_sort_impl(array, |e| {
+        e.clone().try_into().map_err(|_| {
+            "Conversion to isize failed. All elements of the array must be convertible to isize."
+                .to_string()
+        })
+    })
+}
+
+/// It takes a function that extracts an integer key from each element,
+/// and sorts in ascending order of the keys an array whose keys are all the integers in a range,
+/// each exactly once. It returns how many elements were written.
+///
+/// ```rust
+/// use buldak::cycle;
+///
+/// let mut rows = [(2, "c"), (0, "a"), (1, "b")];
+/// assert_eq!(cycle::sort_permutation_by_key(&mut rows, |e| e.0), Ok(3));
+/// assert_eq!(rows, [(0, "a"), (1, "b"), (2, "c")]);
+/// ```
+pub fn sort_permutation_by_key<T, F>(array: &mut [T], key: F) -> Result<usize, String>
+where
+    T: std::clone::Clone,
+    F: Fn(&T) -> isize,
+{
+    _permutation_sort_impl(array, |e| Ok(key(e)))
+}
+
+fn _permutation_sort_impl<T, F>(array: &mut [T], key: F) -> Result<usize, String>
+where
+    T: std::clone::Clone,
+    F: Fn(&T) -> Result<isize, String>,
+{
+    let keys = array
+        .iter()
+        .map(key)
+        .collect::<Result<Vec<isize>, String>>()?;
+    let min = match keys.iter().min() {
+        Some(&min) => min,
+        None => return Ok(0),
+    };
+
+    // the position of every element, once every one of them is checked to be unique
+    let mut positions = Vec::with_capacity(keys.len());
+    let mut seen = vec![false; keys.len()];
+    for &k in keys.iter() {
+        let position = (k as i128 - min as i128) as usize;
+        if position >= keys.len() || seen[position] {
+            return Err("The elements are not a permutation of a range of integers.".to_string());
+        }
+        seen[position] = true;
+        positions.push(position);
+    }
+
+    // Each cycle is rotated by carrying one element, so every misplaced element is written once.
+    let mut writes = 0;
+    for cycle_start in 0..array.len() {
+        if positions[cycle_start] == cycle_start {
+            continue;
+        }
+
+        let mut item = array[cycle_start].clone();
+        let mut pos = positions[cycle_start];
+        loop {
+            item = std::mem::replace(&mut array[pos], item);
+            let next = std::mem::replace(&mut positions[pos], pos);
+            writes += 1;
+
+            if pos == cycle_start {
+                break;
+            }
+            pos = next;
+        }
+    }
+
+    Ok(writes)
+}
+
+// Returns how many elements were written.
+fn _cycle_sort_impl<T, F, W>(array: &mut [T], compare: F, mut write: W) -> usize
+where
+    T: std::clone::Clone,
     F: Fn(&T, &T) -> std::cmp::Ordering,
+    W: FnMut(usize, &T),
 {
     let n = array.len();
+    let mut writes = 0;
 
     // traverse array elements and put it to on
     // the right place
-    for cycle_start in 0..=(n - 2) {
+    for cycle_start in 0..n.saturating_sub(1) {
         // initialize item as starting point
         let mut item = array[cycle_start].clone();
 
@@ -86,9 +252,9 @@ where
         }
 
         // swap
-        let temp = item.clone();
-        item = array[pos].clone();
-        array[pos] = temp;
+        item = std::mem::replace(&mut array[pos], item);
+        write(pos, &array[pos]);
+        writes += 1;
 
         // Rotate rest of the cycle
         while pos != cycle_start {
@@ -107,11 +273,13 @@ where
             }
 
             // swap
-            let temp = item.clone();
-            item = array[pos].clone();
-            array[pos] = temp;
+            item = std::mem::replace(&mut array[pos], item);
+            write(pos, &array[pos]);
+            writes += 1;
         }
     }
+
+    writes
 }
 
 mod tests {
@@ -152,4 +320,42 @@ mod tests {
             assert_eq!(actual, case.expected);
         }
     }
+
+    #[test]
+    fn sort_counting_writes() {
+        let input = vec![5, 1, 4, 2, 2, 3, 0];
+        let mut expected = input.clone();
+        expected.sort();
+
+        let mut actual = input.clone();
+        let mut written = vec![];
+        let writes = super::sort_by_with_writes(
+            &mut actual,
+            |l, r| l.cmp(r),
+            |index, _| written.push(index),
+        );
+        assert_eq!(actual, expected);
+        assert_eq!(writes, written.len());
+        assert!(writes <= input.len());
+
+        let mut actual = input;
+        assert_eq!(super::sort_counting_writes(&mut actual), writes);
+        assert_eq!(super::sort_counting_writes(&mut actual), 0);
+
+        let mut actual: Vec<i32> = vec![];
+        assert_eq!(super::sort_counting_writes(&mut actual), 0);
+        assert_eq!(super::sort_permutation(&mut actual), Ok(0));
+
+        let input: Vec<i64> = (0..1000).map(|i| (i * 7) % 1000 - 500).collect();
+        let mut expected = input.clone();
+        expected.sort();
+        let misplaced = input.iter().zip(&expected).filter(|(l, r)| l != r).count();
+        let mut actual = input.clone();
+        assert_eq!(super::sort_permutation(&mut actual), Ok(misplaced));
+        assert_eq!(actual, expected);
+
+        let mut actual = vec![2, 0, 2];
+        assert!(super::sort_permutation(&mut actual).is_err());
+        assert_eq!(actual, vec![2, 0, 2]);
+    }
 }
@@ -52,13 +52,13 @@ where
     T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering,
 {
-    _double_selection_sort_impl(array, compare)
+    _double_selection_sort_impl(array, compare, utils::swap)
 }
 
-fn _double_selection_sort_impl<T, F>(array: &mut [T], compare: F)
+pub(crate) fn _double_selection_sort_impl<T, F, S>(array: &mut [T], compare: F, mut swap: S)
 where
-    T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering,
+    S: FnMut(&mut [T], usize, usize),
 {
     let mut first = 0;
     let mut last = array.len() - 1;
@@ -86,8 +86,8 @@ where
         if first == max_i {
             max_i = min_i;
         }
-        utils::swap(array, first, min_i);
-        utils::swap(array, last, max_i);
+        swap(array, first, min_i);
+        swap(array, last, max_i);
 
         first += 1;
         last -= 1;
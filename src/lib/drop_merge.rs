@@ -0,0 +1,195 @@
+//! drop-merge sort algorithm.
+//!
+//! Like a stalin sort, the elements that break the order are dropped,
+//! but the dropped elements are then sorted with an intro sort and merged back.
+//!
+//! unstable sort  
+//! **O(N+Klog₂K)**: K=the number of elements out of order
+
+mod utils;
+
+/// How many elements in a row may be dropped before the last kept element is dropped instead.
+const RECENCY: usize = 8;
+
+/// Sort in ascending order using a drop-merge sort algorithm.
+///
+/// ```rust
+/// use buldak::drop_merge;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// drop_merge::sort(&mut nums);
+/// assert_eq!(nums, [1, 2, 3, 4, 5, 13, 21, 111, 234]);
+/// ```
+pub fn sort<T>(array: &mut [T])
+where
+    T: std::cmp::Ord + std::clone::Clone,
+{
+    sort_by(array, |l, r| l.cmp(r))
+}
+
+/// Sort in descending order using a drop-merge sort algorithm.
+///
+/// ```rust
+/// use buldak::drop_merge;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// drop_merge::sort_reverse(&mut nums);
+/// assert_eq!(nums, [234, 111, 21, 13, 5, 4, 3, 2, 1]);
+/// ```
+pub fn sort_reverse<T>(array: &mut [T])
+where
+    T: std::cmp::Ord + std::clone::Clone,
+{
+    sort_by(array, |l, r| l.cmp(r).reverse())
+}
+
+/// It takes a comparator function to determine the order,
+/// and sorts it using a drop-merge sort algorithm.
+///
+/// ```rust
+/// use buldak::drop_merge;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// drop_merge::sort_by(&mut nums, |l, r| l.cmp(r));
+/// assert_eq!(nums, [1, 2, 3, 4, 5, 13, 21, 111, 234]);
+/// ```
+pub fn sort_by<T, F>(array: &mut [T], compare: F)
+where
+    T: std::clone::Clone,
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    _drop_merge_sort_impl(array, compare)
+}
+
+fn _drop_merge_sort_impl<T, F>(array: &mut [T], compare: F)
+where
+    T: std::clone::Clone,
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    use std::cmp::Ordering::Less;
+
+    let len = array.len();
+    if len <= 1 {
+        return;
+    }
+
+    // array[..kept] is the sorted run that is kept, array[kept..read] is free.
+    let mut dropped: Vec<T> = vec![];
+    let mut dropped_in_row = 0;
+    let mut kept = 0;
+    let mut read = 0;
+
+    while read < len {
+        if kept > 0 && compare(&array[read], &array[kept - 1]) == Less {
+            if dropped_in_row == 0 && kept > 1 && compare(&array[read], &array[kept - 2]) != Less {
+                // the last kept element is the odd one out, so drop it instead
+                dropped.push(array[kept - 1].clone());
+                utils::swap(array, kept - 1, read);
+                read += 1;
+            } else if dropped_in_row < RECENCY {
+                dropped.push(array[read].clone());
+                dropped_in_row += 1;
+                read += 1;
+            } else {
+                // Too many drops in a row: the last kept element was probably too large.
+                // Take the recent drops back to read them again, and drop that element.
+                for _ in 0..dropped_in_row {
+                    read -= 1;
+                    array[read] = dropped.pop().unwrap();
+                }
+                kept -= 1;
+                dropped.push(array[kept].clone());
+                dropped_in_row = 0;
+            }
+        } else {
+            utils::swap(array, kept, read);
+            kept += 1;
+            read += 1;
+            dropped_in_row = 0;
+        }
+    }
+
+    crate::intro::sort_by(&mut dropped, |l, r| compare(l, r));
+
+    // Merge from the back, so the kept run never gets overwritten before it is moved.
+    let mut write = len;
+    while let Some(e) = dropped.pop() {
+        while kept > 0 && compare(&array[kept - 1], &e) == std::cmp::Ordering::Greater {
+            write -= 1;
+            kept -= 1;
+            utils::swap(array, write, kept);
+        }
+        write -= 1;
+        array[write] = e;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn sort_ascending() {
+        struct TestCase {
+            input: Vec<i32>,
+            expected: Vec<i32>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                input: vec![1, 4, 2, 3, 5, 111, 234, 21, 13],
+                expected: vec![1, 2, 3, 4, 5, 13, 21, 111, 234],
+            },
+            TestCase {
+                input: vec![100, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+                expected: vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 100],
+            },
+            TestCase {
+                input: vec![],
+                expected: vec![],
+            },
+        ];
+
+        for case in test_cases {
+            let mut actual = case.input.clone();
+            super::sort(&mut actual);
+            assert_eq!(actual, case.expected);
+        }
+    }
+
+    #[test]
+    fn sort_descending() {
+        struct TestCase {
+            input: Vec<i32>,
+            expected: Vec<i32>,
+        }
+
+        let test_cases = vec![TestCase {
+            input: vec![1, 4, 2, 3, 5, 111, 234, 21, 13],
+            expected: vec![234, 111, 21, 13, 5, 4, 3, 2, 1],
+        }];
+
+        for case in test_cases {
+            let mut actual = case.input.clone();
+            super::sort_reverse(&mut actual);
+            assert_eq!(actual, case.expected);
+        }
+    }
+
+    #[test]
+    fn sort_nearly_sorted() {
+        let mut input: Vec<i64> = (0..5_000).collect();
+        for i in (0..input.len()).step_by(37) {
+            input[i] = (i as i64 * 7919) % 5_000;
+        }
+
+        let mut expected = input.clone();
+        expected.sort();
+        let mut actual = input.clone();
+        super::sort(&mut actual);
+        assert_eq!(actual, expected);
+
+        let mut actual = input;
+        actual.reverse();
+        super::sort(&mut actual);
+        assert_eq!(actual, expected);
+    }
+}
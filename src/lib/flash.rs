@@ -0,0 +1,226 @@
+//! flash sort algorithm.
+//!
+//! Neubert's flashsort: the elements are classified by interpolating between the min and the max,
+//! permuted in place into their classes, and finished with an insertion sort.
+//!
+//! unstable sort
+//! **O(N)**: for uniformly distributed values
+
+mod utils;
+
+/// Sort in ascending order using a flash sort algorithm.
+///
+/// ```rust
+/// use buldak::flash;
+///
+/// let mut nums = [1, 4, 2, 3, 5, -44, 111, 234, 21, 13];
+/// flash::sort(&mut nums).unwrap();
+/// assert_eq!(nums, [-44, 1, 2, 3, 4, 5, 13, 21, 111, 234]);
+/// ```
+pub fn sort<T>(array: &mut [T]) -> Result<(), String>
+where
+    T: std::convert::TryInto<isize> + std::clone::Clone,
+{
+    _flash_sort_impl(array, _to_isize, true)
+}
+
+/// Sort in descending order using a flash sort algorithm.
+///
+/// ```rust
+/// use buldak::flash;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13, -2];
+/// flash::sort_reverse(&mut nums).unwrap();
+/// assert_eq!(nums, [234, 111, 21, 13, 5, 4, 3, 2, 1, -2]);
+/// ```
+pub fn sort_reverse<T>(array: &mut [T]) -> Result<(), String>
+where
+    T: std::convert::TryInto<isize> + std::clone::Clone,
+{
+    _flash_sort_impl(array, _to_isize, false)
+}
+
+/// It takes a function that extracts an integer key from each element,
+/// and sorts it in ascending order of the keys using a flash sort algorithm.
+///
+/// ```rust
+/// use buldak::flash;
+///
+/// let mut readings = [(21, "b"), (19, "a"), (25, "a"), (20, "c")];
+/// flash::sort_by_key(&mut readings, |e| e.0).unwrap();
+/// assert_eq!(readings, [(19, "a"), (20, "c"), (21, "b"), (25, "a")]);
+/// ```
+pub fn sort_by_key<T, F>(array: &mut [T], key: F) -> Result<(), String>
+where
+    F: Fn(&T) -> isize,
+{
+    _flash_sort_impl(array, |e| Ok(key(e)), true)
+}
+
+/// It takes a function that extracts an integer key from each element,
+/// and sorts it in descending order of the keys using a flash sort algorithm.
+///
+/// ```rust
+/// use buldak::flash;
+///
+/// let mut readings = [(21, "b"), (19, "a"), (25, "a"), (20, "c")];
+/// flash::sort_by_key_reverse(&mut readings, |e| e.0).unwrap();
+/// assert_eq!(readings, [(25, "a"), (21, "b"), (20, "c"), (19, "a")]);
+/// ```
+pub fn sort_by_key_reverse<T, F>(array: &mut [T], key: F) -> Result<(), String>
+where
+    F: Fn(&T) -> isize,
+{
+    _flash_sort_impl(array, |e| Ok(key(e)), false)
+}
+
+fn _to_isize<T>(e: &T) -> Result<isize, String>
+where
+    T: std::convert::TryInto<isize> + std::clone::Clone,
+{
+    e.clone().try_into().map_err(|_| {
+        "Conversion to isize failed. All elements of the array must be convertible to isize."
+            .to_string()
+    })
+}
+
+// The keys are computed once and moved along with their elements.
+fn _flash_sort_impl<T, F>(array: &mut [T], key: F, asc: bool) -> Result<(), String>
+where
+    F: Fn(&T) -> Result<isize, String>,
+{
+    let len = array.len();
+    if len <= 1 {
+        return Ok(());
+    }
+
+    let mut keys = array
+        .iter()
+        .map(key)
+        .collect::<Result<Vec<isize>, String>>()?;
+    let min = *keys.iter().min().unwrap();
+    let max = *keys.iter().max().unwrap();
+    if min == max {
+        return Ok(());
+    }
+
+    // Neubert's choice of about 0.43N classes
+    let classes = std::cmp::max(len * 43 / 100, 2);
+    let span = (max as i128 - min as i128) as u128;
+    let class_of = |k: isize| {
+        let class = ((k as i128 - min as i128) as u128 * (classes - 1) as u128 / span) as usize;
+        if asc {
+            class
+        } else {
+            classes - 1 - class
+        }
+    };
+
+    // ends[c] is one past the last position of class c
+    let mut ends = vec![0usize; classes];
+    for &k in keys.iter() {
+        ends[class_of(k)] += 1;
+    }
+    for c in 1..classes {
+        ends[c] += ends[c - 1];
+    }
+
+    // Everything before `start` is in its class already. The element at `start` is swapped
+    // to the top free slot of its class until one that belongs at `start` comes back.
+    for start in 0..len {
+        loop {
+            let class = class_of(keys[start]);
+            if start >= ends[class] {
+                break;
+            }
+
+            ends[class] -= 1;
+            utils::swap(array, start, ends[class]);
+            keys.swap(start, ends[class]);
+        }
+    }
+
+    _insertion_sort(array, &mut keys, asc);
+
+    Ok(())
+}
+
+fn _insertion_sort<T>(array: &mut [T], keys: &mut [isize], asc: bool) {
+    let out_of_order = |l: isize, r: isize| if asc { l > r } else { l < r };
+
+    for i in 1..array.len() {
+        let mut j = i;
+        while j > 0 && out_of_order(keys[j - 1], keys[j]) {
+            utils::swap(array, j - 1, j);
+            keys.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn sort_ascending() {
+        struct TestCase {
+            input: Vec<i32>,
+            expected: Vec<i32>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                input: vec![1, 4, 2, 3, 5, 111, 234, 21, 13],
+                expected: vec![1, 2, 3, 4, 5, 13, 21, 111, 234],
+            },
+            TestCase {
+                input: vec![i32::MAX, 0, i32::MIN, -1, 1],
+                expected: vec![i32::MIN, -1, 0, 1, i32::MAX],
+            },
+            TestCase {
+                input: vec![],
+                expected: vec![],
+            },
+        ];
+
+        for case in test_cases {
+            let mut actual = case.input.clone();
+            super::sort(&mut actual).unwrap();
+            assert_eq!(actual, case.expected);
+        }
+    }
+
+    #[test]
+    fn sort_descending() {
+        struct TestCase {
+            input: Vec<i32>,
+            expected: Vec<i32>,
+        }
+
+        let test_cases = vec![TestCase {
+            input: vec![1, 4, 2, 3, 5, 111, 234, 21, 13],
+            expected: vec![234, 111, 21, 13, 5, 4, 3, 2, 1],
+        }];
+
+        for case in test_cases {
+            let mut actual = case.input.clone();
+            super::sort_reverse(&mut actual).unwrap();
+            assert_eq!(actual, case.expected);
+        }
+    }
+
+    #[test]
+    fn sort_large() {
+        let input: Vec<i64> = (0..10_000i64).map(|i| (i * 7919) % 4099 - 2000).collect();
+
+        let mut expected = input.clone();
+        expected.sort();
+        let mut actual = input.clone();
+        super::sort(&mut actual).unwrap();
+        assert_eq!(actual, expected);
+
+        expected.reverse();
+        let mut actual = input;
+        super::sort_reverse(&mut actual).unwrap();
+        assert_eq!(actual, expected);
+    }
+}
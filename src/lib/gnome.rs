@@ -52,13 +52,13 @@ where
     T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering,
 {
-    _gnome_sort_impl(array, compare);
+    _gnome_sort_impl(array, compare, utils::swap);
 }
 
-fn _gnome_sort_impl<T, F>(array: &mut [T], compare: F)
+pub(crate) fn _gnome_sort_impl<T, F, S>(array: &mut [T], compare: F, mut swap: S)
 where
-    T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering,
+    S: FnMut(&mut [T], usize, usize),
 {
     let mut pos = 0;
 
@@ -66,7 +66,7 @@ where
         if pos == 0 || compare(&array[pos], &array[pos - 1]) != std::cmp::Ordering::Less {
             pos += 1;
         } else {
-            utils::swap(array, pos, pos - 1);
+            swap(array, pos, pos - 1);
             pos -= 1;
         }
     }
@@ -2,29 +2,29 @@
 //!
 //! Also called 'bead sort'  
 //!
-//! **O(S)**  
-//! Dropping each and every bead’ as a separate operation since S is the sum of all the beads.  
-//! very slow
+//! **O(N+max)**  
+//! Instead of dropping each and every bead, only the number of beads on each post is kept.  
+//! Negative values are dropped on a separate abacus.
 
-/// Sort in ascending order using a sleep sort algorithm.
+/// The default limit on the memory used for the posts of the abacus, in bytes.
+pub const DEFAULT_MEMORY_LIMIT: usize = 1 << 28;
+
+/// Sort in ascending order using a gravity sort algorithm.
+///
+/// It fails if the posts for the largest absolute values need more than [`DEFAULT_MEMORY_LIMIT`].
 ///
 /// ```rust
 /// use buldak::gravity;
 ///
-/// let mut nums = [6, 4, 2, 3, 1, 5];
-/// gravity::sort(&mut nums);
-/// assert_eq!(nums, [1, 2, 3, 4, 5, 6]);
+/// let mut nums = [6, -4, 2, 3, -1, 5];
+/// gravity::sort(&mut nums).unwrap();
+/// assert_eq!(nums, [-4, -1, 2, 3, 5, 6]);
 /// ```
 pub fn sort<T>(array: &mut [T]) -> Result<(), String>
 where
-    T: std::cmp::Ord
-        + std::convert::TryInto<usize>
-        + std::convert::TryFrom<usize>
-        + std::clone::Clone,
-    <T as std::convert::TryInto<usize>>::Error: std::fmt::Debug,
-    <T as std::convert::TryFrom<usize>>::Error: std::fmt::Debug,
+    T: std::convert::TryInto<isize> + std::convert::TryFrom<isize> + std::clone::Clone,
 {
-    _gravity_sort_impl(array, true)
+    _gravity_sort_impl(array, DEFAULT_MEMORY_LIMIT, true)
 }
 
 /// Sort in descending order using a gravity algorithm.
@@ -32,80 +32,133 @@ where
 /// ```rust
 /// use buldak::gravity;
 ///
+/// let mut nums = [6, -4, 2, 3, -1, 5];
+/// gravity::sort_reverse(&mut nums).unwrap();
+/// assert_eq!(nums, [6, 5, 3, 2, -1, -4]);
+/// ```
+pub fn sort_reverse<T>(array: &mut [T]) -> Result<(), String>
+where
+    T: std::convert::TryInto<isize> + std::convert::TryFrom<isize> + std::clone::Clone,
+{
+    _gravity_sort_impl(array, DEFAULT_MEMORY_LIMIT, false)
+}
+
+/// Sort in ascending order using a gravity sort algorithm,
+/// failing if the posts of the abacus need more than `memory_limit` bytes.
+///
+/// ```rust
+/// use buldak::gravity;
+///
 /// let mut nums = [6, 4, 2, 3, 1, 5];
-/// gravity::sort_reverse(&mut nums);
+/// gravity::sort_with_memory_limit(&mut nums, 1024).unwrap();
+/// assert_eq!(nums, [1, 2, 3, 4, 5, 6]);
+///
+/// let mut nums = [1_000_000, 1];
+/// assert!(gravity::sort_with_memory_limit(&mut nums, 1024).is_err());
+/// ```
+pub fn sort_with_memory_limit<T>(array: &mut [T], memory_limit: usize) -> Result<(), String>
+where
+    T: std::convert::TryInto<isize> + std::convert::TryFrom<isize> + std::clone::Clone,
+{
+    _gravity_sort_impl(array, memory_limit, true)
+}
+
+/// Sort in descending order using a gravity sort algorithm,
+/// failing if the posts of the abacus need more than `memory_limit` bytes.
+///
+/// ```rust
+/// use buldak::gravity;
+///
+/// let mut nums = [6, 4, 2, 3, 1, 5];
+/// gravity::sort_reverse_with_memory_limit(&mut nums, 1024).unwrap();
 /// assert_eq!(nums, [6, 5, 4, 3, 2, 1]);
 /// ```
-pub fn sort_reverse<T>(array: &mut [T]) -> Result<(), String>
+pub fn sort_reverse_with_memory_limit<T>(array: &mut [T], memory_limit: usize) -> Result<(), String>
 where
-    T: std::cmp::Ord
-        + std::convert::TryInto<usize>
-        + std::convert::TryFrom<usize>
-        + std::clone::Clone,
-    <T as std::convert::TryInto<usize>>::Error: std::fmt::Debug,
-    <T as std::convert::TryFrom<usize>>::Error: std::fmt::Debug,
+    T: std::convert::TryInto<isize> + std::convert::TryFrom<isize> + std::clone::Clone,
 {
-    _gravity_sort_impl(array, false)
+    _gravity_sort_impl(array, memory_limit, false)
 }
 
-fn _gravity_sort_impl<T>(array: &mut [T], asc: bool) -> Result<(), String>
+fn _gravity_sort_impl<T>(array: &mut [T], memory_limit: usize, asc: bool) -> Result<(), String>
 where
-    T: std::cmp::Ord
-        + std::convert::TryInto<usize>
-        + std::convert::TryFrom<usize>
-        + std::clone::Clone,
-    <T as std::convert::TryInto<usize>>::Error: std::fmt::Debug,
-    <T as std::convert::TryFrom<usize>>::Error: std::fmt::Debug,
+    T: std::convert::TryInto<isize> + std::convert::TryFrom<isize> + std::clone::Clone,
 {
-    // Find the maximum element
-    let max: usize = array.iter().cloned().max().unwrap().try_into().unwrap();
+    let mut positive = vec![];
+    let mut negative = vec![];
 
-    let mut beads = vec![false; max * array.len()];
+    for e in array.iter() {
+        let e: isize = e.clone().try_into().map_err(|_| {
+            "Conversion to isize failed. All elements of the array must be convertible to isize."
+                .to_string()
+        })?;
 
-    // mark the beads
-    for i in 0..array.len() {
-        let len: usize = array[i].to_owned().try_into().unwrap();
-        for j in 0..len {
-            beads[i * max + j] = true;
+        if e >= 0 {
+            positive.push(e.unsigned_abs());
+        } else {
+            negative.push(e.unsigned_abs());
         }
     }
 
-    for j in 0..max {
-        // count how many beads are on each post
-        let mut sum = 0;
-        for i in 0..array.len() {
-            if beads[i * max + j] {
-                sum += 1;
-            }
-            beads[i * max + j] = false;
-        }
+    let posts = positive.iter().max().unwrap_or(&0) + negative.iter().max().unwrap_or(&0);
+    if posts.saturating_mul(std::mem::size_of::<usize>()) > memory_limit {
+        return Err("The abacus needs more memory than the memory limit.".to_string());
+    }
 
-        // Move beads down
-        for i in (array.len() - sum)..array.len() {
-            beads[i * max + j] = true;
-        }
+    let positive = _drop_beads(&positive);
+    let negative = _drop_beads(&negative);
+
+    // ascending: the largest negative absolute value comes first.
+    // The absolute value of isize::MIN wraps back to it when negated.
+    let sorted: Vec<isize> = if asc {
+        negative
+            .iter()
+            .rev()
+            .map(|&e| (e as isize).wrapping_neg())
+            .chain(positive.iter().map(|&e| e as isize))
+            .collect()
+    } else {
+        positive
+            .iter()
+            .rev()
+            .map(|&e| e as isize)
+            .chain(negative.iter().map(|&e| (e as isize).wrapping_neg()))
+            .collect()
+    };
+
+    for (i, e) in sorted.into_iter().enumerate() {
+        array[i] = T::try_from(e).map_err(|_| "Conversion from isize failed.".to_string())?;
     }
 
-    // Put sorted values in array using beads
-    if asc {
-        for i in 0..array.len() {
-            let mut j = 0;
-            while j < max && beads[i * max + j] {
-                j += 1;
-            }
-            array[i] = T::try_from(j).unwrap();
+    Ok(())
+}
+
+// posts[j] is the number of beads on post j once they have fallen,
+// which is how many values are greater than j.
+// Row r from the bottom then holds as many beads as there are posts higher than r.
+fn _drop_beads(values: &[usize]) -> Vec<usize> {
+    let max = values.iter().copied().max().unwrap_or(0);
+
+    let mut posts = vec![0usize; max];
+    for &e in values.iter() {
+        if e > 0 {
+            posts[e - 1] += 1;
         }
-    } else {
-        for i in 0..array.len() {
-            let mut j = 0;
-            while j < max && beads[i * max + j] {
-                j += 1;
-            }
-            array[array.len() - 1 - i] = T::try_from(j).unwrap();
+    }
+    for j in (1..max).rev() {
+        posts[j - 1] += posts[j];
+    }
+
+    let mut sorted = vec![0; values.len()];
+    let mut width = max;
+    for row in 0..values.len() {
+        while width > 0 && posts[width - 1] <= row {
+            width -= 1;
         }
+        sorted[values.len() - 1 - row] = width;
     }
 
-    return Ok(());
+    sorted
 }
 
 mod tests {
@@ -146,4 +199,26 @@ mod tests {
             assert_eq!(actual, case.expected);
         }
     }
+
+    #[test]
+    fn sort_signed() {
+        let mut actual = vec![0, -3, 7, -3, 12, -100, 1, 0];
+        super::sort(&mut actual).unwrap();
+        assert_eq!(actual, vec![-100, -3, -3, 0, 0, 1, 7, 12]);
+
+        super::sort_reverse(&mut actual).unwrap();
+        assert_eq!(actual, vec![12, 7, 1, 0, 0, -3, -3, -100]);
+
+        let mut actual: Vec<i64> = vec![];
+        super::sort(&mut actual).unwrap();
+        assert_eq!(actual, vec![]);
+
+        let mut actual = vec![1, i64::MAX];
+        assert!(super::sort(&mut actual).is_err());
+        assert_eq!(actual, vec![1, i64::MAX]);
+
+        let mut actual = vec![1, isize::MIN];
+        assert!(super::sort(&mut actual).is_err());
+        assert_eq!(actual, vec![1, isize::MIN]);
+    }
 }
@@ -52,25 +52,25 @@ where
     T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
 {
-    _heap_sort_impl(array, compare)
+    _heap_sort_impl(array, compare, utils::swap)
 }
 
-fn _heap_sort_impl<T, F>(array: &mut [T], compare: F)
+pub(crate) fn _heap_sort_impl<T, F, S>(array: &mut [T], compare: F, mut swap: S)
 where
-    T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
+    S: FnMut(&mut [T], usize, usize),
 {
-    _make_heap(array, array.len(), compare.clone());
+    _make_heap(array, array.len(), compare.clone(), &mut swap);
     for i in (0..array.len()).rev() {
-        utils::swap(array, 0, i);
-        _make_heap(array, i, compare.clone());
+        swap(array, 0, i);
+        _make_heap(array, i, compare.clone(), &mut swap);
     }
 }
 
-fn _make_heap<T, F>(array: &mut [T], len: usize, compare: F)
+fn _make_heap<T, F, S>(array: &mut [T], len: usize, compare: F, swap: &mut S)
 where
-    T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
+    S: FnMut(&mut [T], usize, usize),
 {
     for i in 1..len {
         let mut child = i;
@@ -78,13 +78,50 @@ where
         while child > 0 {
             let root = (child - 1) / 2;
             if compare(&array[root], &array[child]) == std::cmp::Ordering::Less {
-                utils::swap(array, root, child);
+                swap(array, root, child);
             }
             child = root;
         }
     }
 }
 
+// Moves array[root] down until it's not less than its children in array[..len].
+pub(crate) fn _sift_down<T, F>(array: &mut [T], mut root: usize, len: usize, compare: &F)
+where
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    loop {
+        let mut child = root * 2 + 1;
+        if child >= len {
+            break;
+        }
+        if child + 1 < len && compare(&array[child], &array[child + 1]) == std::cmp::Ordering::Less
+        {
+            child += 1;
+        }
+        if compare(&array[root], &array[child]) != std::cmp::Ordering::Less {
+            break;
+        }
+        utils::swap(array, root, child);
+        root = child;
+    }
+}
+
+// Moves array[child] up until it's not greater than its parent.
+pub(crate) fn _sift_up<T, F>(array: &mut [T], mut child: usize, compare: &F)
+where
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    while child > 0 {
+        let root = (child - 1) / 2;
+        if compare(&array[root], &array[child]) != std::cmp::Ordering::Less {
+            break;
+        }
+        utils::swap(array, root, child);
+        child = root;
+    }
+}
+
 mod tests {
     #[test]
     fn sort_ascending() {
@@ -47,7 +47,7 @@ where
 /// ```
 pub fn sort_by<T, F>(array: &mut [T], compare: F)
 where
-    T: std::cmp::Ord + std::clone::Clone,
+    T: std::clone::Clone,
     F: Fn(&T, &T) -> std::cmp::Ordering,
 {
     _insertion_sort_impl(array, compare)
@@ -55,13 +55,13 @@ where
 
 fn _insertion_sort_impl<T, F>(array: &mut [T], compare: F)
 where
-    T: std::cmp::Ord + std::clone::Clone,
+    T: std::clone::Clone,
     F: Fn(&T, &T) -> std::cmp::Ordering,
 {
     let mut start = 1;
     let end = array.len();
 
-    while start != end {
+    while start < end {
         let target = array[start].clone();
 
         let mut back = start as isize - 1;
@@ -51,7 +51,7 @@ where
 /// ```
 pub fn sort_by<T, F>(array: &mut [T], compare: F)
 where
-    T: std::cmp::Ord + std::clone::Clone,
+    T: std::clone::Clone,
     F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
 {
     _intro_sort_impl(array, compare)
@@ -59,9 +59,13 @@ where
 
 fn _intro_sort_impl<T, F>(array: &mut [T], compare: F)
 where
-    T: std::cmp::Ord + std::clone::Clone,
+    T: std::clone::Clone,
     F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
 {
+    if array.len() <= 1 {
+        return;
+    }
+
     let max_depth = (array.len() as f64).log2().floor() as isize * 2;
     _intro_sort_recursive(array, 0, array.len() - 1, max_depth, compare)
 }
@@ -73,7 +77,7 @@ fn _intro_sort_recursive<T, F>(
     mut max_depth: isize,
     compare: F,
 ) where
-    T: std::cmp::Ord + std::clone::Clone,
+    T: std::clone::Clone,
     F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
 {
     if end - begin > 16 {
@@ -93,8 +97,12 @@ fn _intro_sort_recursive<T, F>(
         utils::swap(array, pivot, end);
 
         let pivot = _intro_partition(array, begin, end, compare.clone());
-        _intro_sort_recursive(array, begin, pivot - 1, max_depth, compare.clone());
-        _intro_sort_recursive(array, pivot + 1, end, max_depth, compare);
+        if pivot > begin {
+            _intro_sort_recursive(array, begin, pivot - 1, max_depth, compare.clone());
+        }
+        if pivot < end {
+            _intro_sort_recursive(array, pivot + 1, end, max_depth, compare);
+        }
     } else {
         _insertion_sort(array, begin, end, compare);
     }
@@ -102,7 +110,7 @@ fn _intro_sort_recursive<T, F>(
 
 fn _max_index<T, F>(array: &[T], left: usize, right: usize, compare: F) -> usize
 where
-    T: std::cmp::Ord + std::clone::Clone,
+    T: std::clone::Clone,
     F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
 {
     if compare(&array[left], &array[right]) == std::cmp::Ordering::Less {
@@ -114,7 +122,7 @@ where
 
 fn _min_index<T, F>(array: &[T], left: usize, right: usize, compare: F) -> usize
 where
-    T: std::cmp::Ord + std::clone::Clone,
+    T: std::clone::Clone,
     F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
 {
     if compare(&array[left], &array[right]) != std::cmp::Ordering::Less {
@@ -124,9 +132,15 @@ where
     }
 }
 
-fn _find_pivot<T, F>(array: &[T], left: usize, middle: usize, right: usize, compare: F) -> usize
+pub(crate) fn _find_pivot<T, F>(
+    array: &[T],
+    left: usize,
+    middle: usize,
+    right: usize,
+    compare: F,
+) -> usize
 where
-    T: std::cmp::Ord + std::clone::Clone,
+    T: std::clone::Clone,
     F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
 {
     let max = _max_index(
@@ -152,58 +166,50 @@ where
     return middle;
 }
 
-fn _intro_partition<T, F>(array: &mut [T], left: usize, right: usize, compare: F) -> usize
+pub(crate) fn _intro_partition<T, F>(
+    array: &mut [T],
+    left: usize,
+    right: usize,
+    compare: F,
+) -> usize
 where
-    T: std::cmp::Ord + std::clone::Clone,
+    T: std::clone::Clone,
     F: Fn(&T, &T) -> std::cmp::Ordering,
 {
     let pivot = array[right].clone();
 
-    let mut i = left - 1;
+    let mut i = left;
 
-    for j in left..=(right - 1) {
+    for j in left..right {
         if compare(&array[j], &pivot) != std::cmp::Ordering::Greater {
-            i += 1;
             utils::swap(array, i, j);
+            i += 1;
         }
     }
-    utils::swap(array, i + 1, right);
-    return i + 1;
+    utils::swap(array, i, right);
+    i
 }
 
+// sorts array[begin..=end] with a heap rooted at begin
 fn _heap_sort<T, F>(array: &mut [T], begin: usize, end: usize, compare: F)
 where
-    T: std::cmp::Ord + std::clone::Clone,
-    F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
-{
-    _make_heap(array, begin, end, compare.clone());
-    for i in (begin..end).rev() {
-        utils::swap(array, begin, i);
-        _make_heap(array, begin, i, compare.clone());
-    }
-}
-
-fn _make_heap<T, F>(array: &mut [T], begin: usize, end: usize, compare: F)
-where
-    T: std::cmp::Ord + std::clone::Clone,
-    F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
+    F: Fn(&T, &T) -> std::cmp::Ordering,
 {
-    for i in (begin + 1)..(end) {
-        let mut child = i;
+    let array = &mut array[begin..=end];
+    let len = array.len();
 
-        while child > begin {
-            let root = (child - 1) / 2;
-            if compare(&array[root], &array[child]) == std::cmp::Ordering::Less {
-                utils::swap(array, root, child);
-            }
-            child = root;
-        }
+    for root in (0..len / 2).rev() {
+        crate::heap::_sift_down(array, root, len, &compare);
+    }
+    for last in (1..len).rev() {
+        utils::swap(array, 0, last);
+        crate::heap::_sift_down(array, 0, last, &compare);
     }
 }
 
-fn _insertion_sort<T, F>(array: &mut [T], left: usize, right: usize, compare: F)
+pub(crate) fn _insertion_sort<T, F>(array: &mut [T], left: usize, right: usize, compare: F)
 where
-    T: std::cmp::Ord + std::clone::Clone,
+    T: std::clone::Clone,
     F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
 {
     for i in (left + 1)..=right {
@@ -228,10 +234,16 @@ mod tests {
             expected: Vec<i32>,
         }
 
-        let test_cases = vec![TestCase {
-            input: vec![1, 4, 2, 3, 5, 111, 234, 21, 13],
-            expected: vec![1, 2, 3, 4, 5, 13, 21, 111, 234],
-        }];
+        let test_cases = vec![
+            TestCase {
+                input: vec![1, 4, 2, 3, 5, 111, 234, 21, 13],
+                expected: vec![1, 2, 3, 4, 5, 13, 21, 111, 234],
+            },
+            TestCase {
+                input: (0..100).map(|i| (i * 37) % 10).collect(),
+                expected: (0..100).map(|i| i / 10).collect(),
+            },
+        ];
 
         for case in test_cases {
             let mut actual = case.input.clone();
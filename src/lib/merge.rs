@@ -82,7 +82,6 @@ fn _merge_sort_recursive<T, F>(
         _merge_sort_recursive(array, sorted, left, middle, compare.clone());
         _merge_sort_recursive(array, sorted, middle + 1, right, compare.clone());
         _merge(array, sorted, left, middle, right, compare);
-        println!("{:?}", sorted);
     }
 }
 
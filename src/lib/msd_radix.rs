@@ -0,0 +1,373 @@
+//! msd radix sort algorithm.
+//!
+//! Sorts byte strings from the first byte, so keys of any length can be used.
+//! `sort` is an in-place American flag sort, `sort_stable` moves elements through a buffer.
+//!
+//! unstable sort (`sort`), stable sort (`sort_stable`)
+//! **O(wN)**: w=length of the distinguishing prefix of the keys
+
+mod utils;
+
+/// Buckets with at most this many elements are sorted with an insertion sort.
+const INSERTION_THRESHOLD: usize = 32;
+
+/// A digit for every byte value, and one for keys that have already ended.
+pub(crate) const DIGITS: usize = 257;
+
+/// Sort in ascending order using an American flag sort algorithm.
+///
+/// ```rust
+/// use buldak::msd_radix;
+///
+/// let mut words = ["banana", "apple", "cherry", "app", "", "apricot"];
+/// msd_radix::sort(&mut words);
+/// assert_eq!(words, ["", "app", "apple", "apricot", "banana", "cherry"]);
+/// ```
+pub fn sort<T>(array: &mut [T])
+where
+    T: std::convert::AsRef<[u8]>,
+{
+    _american_flag_sort_impl(array, |e| e.as_ref(), true)
+}
+
+/// Sort in descending order using an American flag sort algorithm.
+///
+/// ```rust
+/// use buldak::msd_radix;
+///
+/// let mut words = ["banana", "apple", "cherry", "app", "", "apricot"];
+/// msd_radix::sort_reverse(&mut words);
+/// assert_eq!(words, ["cherry", "banana", "apricot", "apple", "app", ""]);
+/// ```
+pub fn sort_reverse<T>(array: &mut [T])
+where
+    T: std::convert::AsRef<[u8]>,
+{
+    _american_flag_sort_impl(array, |e| e.as_ref(), false)
+}
+
+/// It takes a function that borrows a byte string key from each element,
+/// and sorts it in ascending order of the keys using an American flag sort algorithm.
+///
+/// ```rust
+/// use buldak::msd_radix;
+///
+/// struct User {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// let mut users = vec![
+///     User { name: "lee".to_string(), age: 24 },
+///     User { name: "kim".to_string(), age: 31 },
+///     User { name: "choi".to_string(), age: 19 },
+/// ];
+/// msd_radix::sort_by_key(&mut users, |e| e.name.as_str());
+/// assert_eq!(users.iter().map(|e| e.age).collect::<Vec<_>>(), [19, 31, 24]);
+/// ```
+pub fn sort_by_key<T, K, F>(array: &mut [T], key: F)
+where
+    K: std::convert::AsRef<[u8]> + ?Sized + 'static,
+    F: Fn(&T) -> &K,
+{
+    _american_flag_sort_impl(array, |e| key(e).as_ref(), true)
+}
+
+/// It takes a function that borrows a byte string key from each element,
+/// and sorts it in descending order of the keys using an American flag sort algorithm.
+///
+/// ```rust
+/// use buldak::msd_radix;
+///
+/// let mut pairs = [(b"ab".to_vec(), 1), (b"b".to_vec(), 2), (b"a".to_vec(), 3)];
+/// msd_radix::sort_by_key_reverse(&mut pairs, |e| &e.0);
+/// assert_eq!(pairs.iter().map(|e| e.1).collect::<Vec<_>>(), [2, 1, 3]);
+/// ```
+pub fn sort_by_key_reverse<T, K, F>(array: &mut [T], key: F)
+where
+    K: std::convert::AsRef<[u8]> + ?Sized + 'static,
+    F: Fn(&T) -> &K,
+{
+    _american_flag_sort_impl(array, |e| key(e).as_ref(), false)
+}
+
+/// Sort in ascending order using a stable msd radix sort algorithm.
+///
+/// ```rust
+/// use buldak::msd_radix;
+///
+/// let mut words = vec![b"banana".to_vec(), b"apple".to_vec(), b"app".to_vec()];
+/// msd_radix::sort_stable(&mut words);
+/// assert_eq!(words, [b"app".to_vec(), b"apple".to_vec(), b"banana".to_vec()]);
+/// ```
+pub fn sort_stable<T>(array: &mut [T])
+where
+    T: std::convert::AsRef<[u8]> + std::clone::Clone,
+{
+    _msd_radix_sort_impl(array, |e| e.as_ref(), true)
+}
+
+/// Sort in descending order using a stable msd radix sort algorithm.
+///
+/// ```rust
+/// use buldak::msd_radix;
+///
+/// let mut words = vec!["banana".to_string(), "apple".to_string(), "app".to_string()];
+/// msd_radix::sort_stable_reverse(&mut words);
+/// assert_eq!(words, ["banana", "apple", "app"]);
+/// ```
+pub fn sort_stable_reverse<T>(array: &mut [T])
+where
+    T: std::convert::AsRef<[u8]> + std::clone::Clone,
+{
+    _msd_radix_sort_impl(array, |e| e.as_ref(), false)
+}
+
+/// It takes a function that borrows a byte string key from each element,
+/// and sorts it in ascending order of the keys using a stable msd radix sort algorithm.
+///
+/// ```rust
+/// use buldak::msd_radix;
+///
+/// let mut pairs = [("kim", 1), ("lee", 2), ("kim", 3), ("choi", 4)];
+/// msd_radix::sort_stable_by_key(&mut pairs, |e| e.0);
+/// assert_eq!(pairs, [("choi", 4), ("kim", 1), ("kim", 3), ("lee", 2)]);
+/// ```
+pub fn sort_stable_by_key<T, K, F>(array: &mut [T], key: F)
+where
+    T: std::clone::Clone,
+    K: std::convert::AsRef<[u8]> + ?Sized + 'static,
+    F: Fn(&T) -> &K,
+{
+    _msd_radix_sort_impl(array, |e| key(e).as_ref(), true)
+}
+
+/// It takes a function that borrows a byte string key from each element,
+/// and sorts it in descending order of the keys using a stable msd radix sort algorithm.
+///
+/// ```rust
+/// use buldak::msd_radix;
+///
+/// let mut pairs = [("kim", 1), ("lee", 2), ("kim", 3), ("choi", 4)];
+/// msd_radix::sort_stable_by_key_reverse(&mut pairs, |e| e.0);
+/// assert_eq!(pairs, [("lee", 2), ("kim", 1), ("kim", 3), ("choi", 4)]);
+/// ```
+pub fn sort_stable_by_key_reverse<T, K, F>(array: &mut [T], key: F)
+where
+    T: std::clone::Clone,
+    K: std::convert::AsRef<[u8]> + ?Sized + 'static,
+    F: Fn(&T) -> &K,
+{
+    _msd_radix_sort_impl(array, |e| key(e).as_ref(), false)
+}
+
+// 0 once the key has ended, so shorter keys come first.
+pub(crate) fn _digit(key: &[u8], depth: usize) -> usize {
+    key.get(depth).map_or(0, |&b| b as usize + 1)
+}
+
+// The position of a digit in the output, walking the buckets backwards for descending order.
+fn _slot(digit: usize, asc: bool) -> usize {
+    if asc {
+        digit
+    } else {
+        DIGITS - 1 - digit
+    }
+}
+
+fn _american_flag_sort_impl<T, F>(array: &mut [T], key: F, asc: bool)
+where
+    F: Fn(&T) -> &[u8],
+{
+    // (begin, end, depth): every key in the range shares its first `depth` bytes
+    let mut stack = vec![(0, array.len(), 0)];
+
+    while let Some((begin, end, depth)) = stack.pop() {
+        if end - begin <= INSERTION_THRESHOLD {
+            _insertion_sort(&mut array[begin..end], &key, depth, asc);
+            continue;
+        }
+
+        let mut counts = [0usize; DIGITS];
+        for e in array[begin..end].iter() {
+            counts[_slot(_digit(key(e), depth), asc)] += 1;
+        }
+
+        let mut heads = [0usize; DIGITS];
+        let mut tails = [0usize; DIGITS];
+        let mut total = begin;
+        for slot in 0..DIGITS {
+            heads[slot] = total;
+            total += counts[slot];
+            tails[slot] = total;
+        }
+
+        // move every element into its bucket by following the cycles of the permutation
+        for slot in 0..DIGITS {
+            while heads[slot] < tails[slot] {
+                let target = _slot(_digit(key(&array[heads[slot]]), depth), asc);
+                if target == slot {
+                    heads[slot] += 1;
+                } else {
+                    utils::swap(array, heads[slot], heads[target]);
+                    heads[target] += 1;
+                }
+            }
+        }
+
+        let mut bucket_begin = begin;
+        for (slot, &count) in counts.iter().enumerate() {
+            if count > 1 && _slot(slot, asc) != 0 {
+                stack.push((bucket_begin, bucket_begin + count, depth + 1));
+            }
+            bucket_begin += count;
+        }
+    }
+}
+
+fn _msd_radix_sort_impl<T, F>(array: &mut [T], key: F, asc: bool)
+where
+    T: std::clone::Clone,
+    F: Fn(&T) -> &[u8],
+{
+    if array.len() <= INSERTION_THRESHOLD {
+        _insertion_sort(array, &key, 0, asc);
+        return;
+    }
+
+    let mut buffer = array.to_vec();
+    let mut stack = vec![(0, array.len(), 0)];
+
+    while let Some((begin, end, depth)) = stack.pop() {
+        if end - begin <= INSERTION_THRESHOLD {
+            _insertion_sort(&mut array[begin..end], &key, depth, asc);
+            continue;
+        }
+
+        let mut counts = [0usize; DIGITS];
+        for e in array[begin..end].iter() {
+            counts[_slot(_digit(key(e), depth), asc)] += 1;
+        }
+
+        let mut offsets = [0usize; DIGITS];
+        let mut total = begin;
+        for slot in 0..DIGITS {
+            offsets[slot] = total;
+            total += counts[slot];
+        }
+
+        for e in array[begin..end].iter() {
+            let slot = _slot(_digit(key(e), depth), asc);
+            buffer[offsets[slot]] = e.clone();
+            offsets[slot] += 1;
+        }
+        array[begin..end].clone_from_slice(&buffer[begin..end]);
+
+        let mut bucket_begin = begin;
+        for (slot, &count) in counts.iter().enumerate() {
+            if count > 1 && _slot(slot, asc) != 0 {
+                stack.push((bucket_begin, bucket_begin + count, depth + 1));
+            }
+            bucket_begin += count;
+        }
+    }
+}
+
+// Stable, and only compares the bytes from `depth` on.
+pub(crate) fn _insertion_sort<T, F>(array: &mut [T], key: &F, depth: usize, asc: bool)
+where
+    F: Fn(&T) -> &[u8],
+{
+    for i in 1..array.len() {
+        let mut j = i;
+        while j > 0 {
+            let (l, r) = (key(&array[j - 1]), key(&array[j]));
+            let order = l[std::cmp::min(depth, l.len())..].cmp(&r[std::cmp::min(depth, r.len())..]);
+            let order = if asc { order } else { order.reverse() };
+            if order != std::cmp::Ordering::Greater {
+                break;
+            }
+            utils::swap(array, j - 1, j);
+            j -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn sort_ascending() {
+        struct TestCase {
+            input: Vec<&'static str>,
+            expected: Vec<&'static str>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                input: vec!["banana", "apple", "cherry", "app", "", "apricot"],
+                expected: vec!["", "app", "apple", "apricot", "banana", "cherry"],
+            },
+            TestCase {
+                input: vec![],
+                expected: vec![],
+            },
+        ];
+
+        for case in test_cases {
+            let mut actual = case.input.clone();
+            super::sort(&mut actual);
+            assert_eq!(actual, case.expected);
+
+            let mut actual = case.input.clone();
+            super::sort_stable(&mut actual);
+            assert_eq!(actual, case.expected);
+        }
+    }
+
+    #[test]
+    fn sort_descending() {
+        struct TestCase {
+            input: Vec<&'static str>,
+            expected: Vec<&'static str>,
+        }
+
+        let test_cases = vec![TestCase {
+            input: vec!["banana", "apple", "cherry", "app", "", "apricot"],
+            expected: vec!["cherry", "banana", "apricot", "apple", "app", ""],
+        }];
+
+        for case in test_cases {
+            let mut actual = case.input.clone();
+            super::sort_reverse(&mut actual);
+            assert_eq!(actual, case.expected);
+
+            let mut actual = case.input.clone();
+            super::sort_stable_reverse(&mut actual);
+            assert_eq!(actual, case.expected);
+        }
+    }
+
+    #[test]
+    fn sort_large() {
+        let input: Vec<Vec<u8>> = (0..5000u32)
+            .map(|i| {
+                let len = (i * 7) % 13;
+                (0..len).map(|j| ((i * 31 + j * 17) % 5) as u8).collect()
+            })
+            .collect();
+
+        let mut expected = input.clone();
+        expected.sort();
+
+        let mut actual = input.clone();
+        super::sort(&mut actual);
+        assert_eq!(actual, expected);
+
+        let mut indexed: Vec<(Vec<u8>, usize)> = input.into_iter().zip(0..).collect();
+        let mut expected = indexed.clone();
+        expected.sort_by(|l, r| r.0.cmp(&l.0));
+
+        super::sort_stable_by_key_reverse(&mut indexed, |e| &e.0);
+        assert_eq!(indexed, expected);
+    }
+}
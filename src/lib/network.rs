@@ -0,0 +1,892 @@
+//! sorting network algorithms.
+//!
+//! Generates sorting networks, applies them to fixed-size arrays,
+//! and validates them with the 0-1 principle.
+//!
+//! unstable sort
+//! **O(1)** for a fixed N: the comparators do not depend on the values
+
+use std::sync::OnceLock;
+
+/// Networks larger than this are not validated, because validation tries 2^N inputs.
+const MAX_VALIDATION_SIZE: usize = 32;
+
+/// The networks for up to this many wires are built once for all threads, on the first sort.
+/// Larger arrays are sorted by Batcher's merge exchange, which is never built.
+const MAX_TABLE_SIZE: usize = 32;
+
+// the optimal networks for 9, 10 and 12 inputs
+#[rustfmt::skip]
+const OPTIMAL_9: [(usize, usize); 25] = [
+    (0, 3), (1, 7), (2, 5), (4, 8),
+    (0, 7), (2, 4), (3, 8), (5, 6),
+    (0, 2), (1, 3), (4, 5), (7, 8),
+    (1, 4), (3, 6), (5, 7),
+    (0, 1), (2, 4), (3, 5), (6, 8),
+    (2, 3), (4, 5), (6, 7),
+    (1, 2), (3, 4), (5, 6),
+];
+
+#[rustfmt::skip]
+const OPTIMAL_10: [(usize, usize); 29] = [
+    (0, 8), (1, 9), (2, 7), (3, 5), (4, 6),
+    (0, 2), (1, 4), (5, 8), (7, 9),
+    (0, 3), (2, 4), (5, 7), (6, 9),
+    (0, 1), (3, 6), (8, 9),
+    (1, 5), (2, 3), (4, 8), (6, 7),
+    (1, 2), (3, 5), (4, 6), (7, 8),
+    (2, 3), (4, 5), (6, 7),
+    (3, 4), (5, 6),
+];
+
+#[rustfmt::skip]
+const OPTIMAL_12: [(usize, usize); 39] = [
+    (0, 8), (1, 7), (2, 6), (3, 11), (4, 10), (5, 9),
+    (0, 1), (2, 5), (3, 4), (6, 9), (7, 8), (10, 11),
+    (0, 2), (1, 6), (5, 10), (9, 11),
+    (0, 3), (1, 2), (4, 6), (5, 7), (8, 11), (9, 10),
+    (1, 4), (3, 5), (6, 8), (7, 10),
+    (1, 3), (2, 5), (6, 9), (8, 10),
+    (2, 3), (4, 5), (6, 7), (8, 9),
+    (4, 6), (5, 7),
+    (3, 4), (5, 6), (7, 8),
+];
+
+// the best known network for 13 inputs, with 45 comparators in 10 layers
+#[rustfmt::skip]
+const BEST_KNOWN_13: [(usize, usize); 45] = [
+    (0, 12), (1, 11), (2, 10), (3, 9), (4, 8), (5, 7),
+    (0, 3), (1, 4), (2, 5), (7, 10), (8, 11), (9, 12),
+    (0, 2), (3, 4), (6, 12), (8, 9), (10, 11),
+    (4, 5), (6, 10), (7, 9), (11, 12),
+    (1, 6), (3, 7), (4, 8), (5, 10), (9, 11),
+    (0, 1), (2, 6), (3, 4), (5, 9), (10, 11),
+    (1, 2), (5, 8), (6, 7), (9, 10),
+    (1, 3), (2, 4), (5, 6), (7, 8),
+    (2, 3), (4, 5), (6, 7), (8, 9),
+    (3, 4), (5, 6),
+];
+
+// networks for 17, 18 and 20 inputs found by a local search over the comparators,
+// a few comparators above the best known: 73 (71), 78 (77) and 93 (91)
+#[rustfmt::skip]
+const SEARCHED_17: [(usize, usize); 73] = [
+    (0, 16), (1, 14), (2, 13), (3, 12), (4, 11), (5, 10), (6, 9), (7, 8),
+    (0, 1), (3, 6), (4, 7), (8, 11), (9, 12), (10, 15), (14, 16),
+    (0, 3), (1, 9), (2, 8), (6, 14), (12, 16), (13, 15),
+    (1, 10), (2, 6), (5, 14), (7, 13), (8, 12), (11, 15),
+    (1, 4), (3, 7), (9, 13), (11, 14), (15, 16),
+    (0, 1), (4, 5), (7, 8), (10, 11), (14, 15),
+    (3, 4), (5, 6), (9, 10), (11, 12),
+    (2, 3), (4, 7), (5, 9), (6, 10), (8, 11), (12, 13),
+    (0, 2), (1, 3), (4, 5), (6, 8), (7, 9), (10, 11), (12, 14), (13, 15),
+    (1, 2), (3, 5), (10, 12), (13, 14),
+    (2, 4), (5, 6), (9, 10), (11, 13),
+    (3, 4), (5, 7), (6, 9), (8, 10), (11, 12),
+    (4, 5), (6, 7), (8, 9), (10, 11),
+    (7, 8),
+];
+
+#[rustfmt::skip]
+const SEARCHED_18: [(usize, usize); 78] = [
+    (0, 17), (1, 16), (2, 15), (3, 14), (4, 13), (5, 12), (6, 11), (7, 10), (8, 9),
+    (0, 2), (1, 6), (4, 7), (5, 8), (9, 12), (10, 13), (11, 16), (15, 17),
+    (0, 4), (1, 3), (2, 10), (7, 15), (13, 17), (14, 16),
+    (1, 5), (2, 11), (3, 9), (6, 15), (8, 14), (12, 16),
+    (0, 1), (2, 5), (3, 7), (4, 8), (9, 13), (10, 14), (12, 15), (16, 17),
+    (1, 2), (5, 6), (8, 9), (11, 12), (15, 16),
+    (4, 5), (6, 7), (10, 11), (12, 13),
+    (3, 4), (5, 8), (6, 10), (7, 11), (9, 12), (13, 14),
+    (1, 3), (2, 4), (5, 6), (7, 9), (8, 10), (11, 12), (13, 15), (14, 16),
+    (2, 3), (4, 6), (11, 13), (14, 15),
+    (3, 5), (6, 7), (10, 11), (12, 14),
+    (4, 5), (6, 8), (7, 10), (9, 11), (12, 13),
+    (5, 6), (7, 8), (9, 10), (11, 12),
+    (8, 9),
+];
+
+#[rustfmt::skip]
+const SEARCHED_20: [(usize, usize); 93] = [
+    (0, 19), (1, 18), (2, 17), (3, 16), (4, 15), (5, 14), (6, 13), (7, 12), (8, 11), (9, 10),
+    (0, 3), (1, 8), (2, 4), (5, 9), (6, 7), (10, 14), (11, 18), (12, 13), (15, 17), (16, 19),
+    (0, 2), (3, 10), (4, 8), (9, 16), (11, 15), (17, 19),
+    (2, 5), (4, 7), (12, 15), (14, 17),
+    (0, 2), (3, 12), (5, 6), (7, 16), (13, 14), (17, 19),
+    (1, 5), (6, 9), (10, 13), (14, 18),
+    (0, 1), (2, 5), (4, 6), (8, 9), (10, 11), (13, 15), (14, 17), (18, 19),
+    (1, 4), (5, 10), (6, 7), (9, 14), (12, 13), (15, 18),
+    (3, 5), (8, 10), (9, 11), (14, 16),
+    (2, 3), (5, 6), (7, 9), (10, 12), (13, 14), (16, 17),
+    (1, 2), (3, 4), (6, 8), (7, 10), (9, 12), (11, 13), (15, 16), (17, 18),
+    (2, 3), (4, 7), (5, 6), (8, 10), (9, 11), (12, 15), (13, 14), (16, 17),
+    (4, 5), (6, 7), (8, 9), (10, 11), (12, 13), (14, 15),
+    (3, 4), (5, 6), (7, 8), (9, 10), (11, 12), (13, 14), (15, 16),
+];
+
+// Green's network after its first four layers, which form a hypercube
+#[rustfmt::skip]
+const GREEN_16_TAIL: [(usize, usize); 28] = [
+    (5, 10), (6, 9), (3, 12), (13, 14), (7, 11), (1, 2), (4, 8),
+    (1, 4), (7, 13), (2, 8), (11, 14), (5, 6), (9, 10),
+    (2, 4), (11, 13), (3, 8), (7, 12),
+    (6, 8), (10, 12), (3, 5), (7, 9),
+    (3, 4), (5, 6), (7, 8), (9, 10), (11, 12),
+    (6, 7), (8, 9),
+];
+
+/// A sorting network on a fixed number of wires.
+///
+/// Each comparator `(i, j)` has `i < j`,
+/// and leaves the smaller element on wire `i` and the larger one on wire `j`.
+///
+/// ```rust
+/// use buldak::network::Network;
+///
+/// let network = Network::smallest_available(4);
+/// assert_eq!(network.len(), 5);
+/// assert!(network.is_sorting_network());
+///
+/// let mut nums = [4, 1, 3, 2];
+/// network.apply(&mut nums).unwrap();
+/// assert_eq!(nums, [1, 2, 3, 4]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Network {
+    size: usize,
+    comparators: Vec<(usize, usize)>,
+}
+
+impl Network {
+    /// Makes a network from a comparator list.
+    ///
+    /// It fails if a comparator is not of the form `(i, j)` with `i < j < size`.
+    /// The list is not checked to be a sorting network; see [`Network::validate`].
+    pub fn new(size: usize, comparators: Vec<(usize, usize)>) -> Result<Network, String> {
+        for &(i, j) in comparators.iter() {
+            if i >= j || j >= size {
+                return Err(format!(
+                    "The comparator ({}, {}) is invalid for a network of size {}.",
+                    i, j, size
+                ));
+            }
+        }
+
+        Ok(Network { size, comparators })
+    }
+
+    /// Batcher's odd-even merge network, in the merge exchange form that works for any size.
+    pub fn odd_even_merge(size: usize) -> Network {
+        let comparators = _merge_exchange_stages(size)
+            .into_iter()
+            .flat_map(|(p, r, d)| {
+                (0..(size - d))
+                    .filter(move |i| i & p == r)
+                    .map(move |i| (i, i + d))
+            })
+            .collect();
+
+        Network { size, comparators }
+    }
+
+    /// Bitonic network.
+    ///
+    /// It is built for the next power of two and the extra wires are pruned.
+    pub fn bitonic(size: usize) -> Network {
+        let full = size.next_power_of_two();
+        let mut comparators = vec![];
+
+        let mut k = 2;
+        while k <= full {
+            // compare each element with its mirror, so every comparator is ascending
+            for block in (0..full).step_by(k) {
+                for x in 0..(k / 2) {
+                    comparators.push((block + x, block + k - 1 - x));
+                }
+            }
+
+            let mut j = k / 4;
+            while j >= 1 {
+                for block in (0..full).step_by(j * 2) {
+                    for x in 0..j {
+                        comparators.push((block + x, block + x + j));
+                    }
+                }
+                j /= 2;
+            }
+
+            k *= 2;
+        }
+
+        Network {
+            size: full,
+            comparators,
+        }
+        ._prune(0, size)
+    }
+
+    /// Parberry's pairwise sorting network.
+    pub fn pairwise(size: usize) -> Network {
+        let mut comparators = vec![];
+
+        let mut a = 1;
+        while a < size {
+            _pairwise_pass(&mut comparators, size, a, 1, a);
+            a *= 2;
+        }
+
+        a /= 4;
+        let mut e = 1;
+        while a > 0 {
+            let mut d = e;
+            while d > 0 {
+                _pairwise_pass(&mut comparators, size, a, d, (d + 1) * a);
+                d /= 2;
+            }
+            a /= 2;
+            e = e * 2 + 1;
+        }
+
+        Network { size, comparators }
+    }
+
+    /// Green's network for 16 inputs, with 60 comparators in 10 layers.
+    pub fn green16() -> Network {
+        let mut comparators = vec![];
+
+        for bit in [1, 2, 4, 8] {
+            for i in 0..16 {
+                if i & bit == 0 {
+                    comparators.push((i, i | bit));
+                }
+            }
+        }
+        comparators.extend_from_slice(&GREEN_16_TAIL);
+
+        Network {
+            size: 16,
+            comparators,
+        }
+    }
+
+    /// The smallest network this module can build for the given size.
+    ///
+    /// It has the best known number of comparators for up to 16 inputs and for 31 and 32.
+    /// Sizes 17 to 20 use searched tables, or 19 the one for 20 pruned, and take 1 to 3 comparators more.
+    /// Sizes 21 to 30 merge Green's network with a smaller one by Batcher's merge and take up to 7 more.
+    /// Above 32 it is the smaller of the odd-even merge and pairwise networks.
+    ///
+    /// ```rust
+    /// use buldak::network::Network;
+    ///
+    /// assert_eq!(Network::smallest_available(10).len(), 29);
+    /// assert_eq!(Network::smallest_available(13).len(), 45);
+    /// assert_eq!(Network::smallest_available(16).len(), 60);
+    /// assert_eq!(Network::smallest_available(32).len(), 185);
+    /// ```
+    pub fn smallest_available(size: usize) -> Network {
+        let mut candidates = vec![Network::odd_even_merge(size), Network::pairwise(size)];
+
+        for larger in _tables() {
+            if larger.size >= size && larger.size - size <= 8 {
+                for low in 0..=(larger.size - size) {
+                    candidates.push(larger._prune(low, size));
+                }
+            }
+        }
+        if size > 16 && size <= 32 {
+            candidates.push(_merge_two(
+                &Network::green16(),
+                &Network::smallest_available(size - 16),
+            ));
+        }
+
+        candidates
+            .into_iter()
+            .min_by_key(|network| (network.len(), network.depth()))
+            .unwrap()
+    }
+
+    /// The number of wires.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The comparators, in the order they are applied.
+    pub fn comparators(&self) -> &[(usize, usize)] {
+        &self.comparators
+    }
+
+    /// The number of comparators.
+    pub fn len(&self) -> usize {
+        self.comparators.len()
+    }
+
+    /// Whether the network has no comparators.
+    pub fn is_empty(&self) -> bool {
+        self.comparators.is_empty()
+    }
+
+    /// Groups the comparators into layers.
+    ///
+    /// A comparator is placed in the first layer after every comparator it depends on,
+    /// so the comparators of a layer touch disjoint wires.
+    pub fn layers(&self) -> Vec<Vec<(usize, usize)>> {
+        let mut wire_depth = vec![0; self.size];
+        let mut layers: Vec<Vec<(usize, usize)>> = vec![];
+
+        for &(i, j) in self.comparators.iter() {
+            let depth = std::cmp::max(wire_depth[i], wire_depth[j]);
+            if depth == layers.len() {
+                layers.push(vec![]);
+            }
+            layers[depth].push((i, j));
+
+            wire_depth[i] = depth + 1;
+            wire_depth[j] = depth + 1;
+        }
+
+        layers
+    }
+
+    /// The number of layers.
+    pub fn depth(&self) -> usize {
+        self.layers().len()
+    }
+
+    /// Applies the network to an array of the same size.
+    pub fn apply<T, const N: usize>(&self, array: &mut [T; N]) -> Result<(), String>
+    where
+        T: std::cmp::Ord,
+    {
+        self.apply_by(array, |l, r| l.cmp(r))
+    }
+
+    /// It takes a comparator function to determine the order,
+    /// and applies the network to a slice of the same size.
+    ///
+    /// ```rust
+    /// use buldak::network::Network;
+    ///
+    /// let mut nums = vec![1, 4, 2, 3, 5];
+    /// Network::odd_even_merge(5).apply_by(&mut nums, |l, r| l.cmp(r).reverse()).unwrap();
+    /// assert_eq!(nums, [5, 4, 3, 2, 1]);
+    /// ```
+    pub fn apply_by<T, F>(&self, array: &mut [T], compare: F) -> Result<(), String>
+    where
+        F: Fn(&T, &T) -> std::cmp::Ordering,
+    {
+        if array.len() != self.size {
+            return Err(format!(
+                "The network has {} wires, but the array has {} elements.",
+                self.size,
+                array.len()
+            ));
+        }
+
+        _apply_comparators(&self.comparators, array, compare, <[T]>::swap);
+
+        Ok(())
+    }
+
+    /// Draws the network with one row per wire and one column per comparator.
+    ///
+    /// Comparators that fit side by side share a column.
+    ///
+    /// ```rust
+    /// use buldak::network::Network;
+    ///
+    /// let diagram = Network::smallest_available(3).to_ascii();
+    /// assert_eq!(
+    ///     diagram,
+    ///     [
+    ///         "0: -o--o-----",
+    ///         "    |  |",
+    ///         "1: -|--o--o--",
+    ///         "    |     |",
+    ///         "2: -o-----o--",
+    ///     ]
+    ///     .join("\n")
+    /// );
+    /// ```
+    pub fn to_ascii(&self) -> String {
+        let mut columns: Vec<Vec<(usize, usize)>> = vec![];
+
+        for mut layer in self.layers() {
+            layer.sort_unstable();
+
+            let mut layer_columns: Vec<Vec<(usize, usize)>> = vec![];
+            for (i, j) in layer {
+                let free = layer_columns
+                    .iter_mut()
+                    .find(|column| column.iter().all(|&(a, b)| b < i || j < a));
+
+                match free {
+                    Some(column) => column.push((i, j)),
+                    None => layer_columns.push(vec![(i, j)]),
+                }
+            }
+            columns.extend(layer_columns);
+        }
+
+        let width = self.size.saturating_sub(1).to_string().len();
+        let mut lines = vec![];
+
+        for wire in 0..self.size {
+            let mut line = format!("{:>width$}: -", wire, width = width);
+            for column in columns.iter() {
+                let cell = if column.iter().any(|&(i, j)| i == wire || j == wire) {
+                    'o'
+                } else if column.iter().any(|&(i, j)| i < wire && wire < j) {
+                    '|'
+                } else {
+                    '-'
+                };
+                line.push(cell);
+                line.push_str("--");
+            }
+            lines.push(line);
+
+            if wire + 1 < self.size {
+                let mut line = " ".repeat(width + 3);
+                for column in columns.iter() {
+                    let cell = if column.iter().any(|&(i, j)| i <= wire && wire < j) {
+                        '|'
+                    } else {
+                        ' '
+                    };
+                    line.push(cell);
+                    line.push_str("  ");
+                }
+                lines.push(line.trim_end().to_string());
+            }
+        }
+
+        lines.join("\n")
+    }
+
+    /// Checks that the network sorts every input, using the 0-1 principle.
+    ///
+    /// The comparators of the first layer touch disjoint wires, so each of their pairs of wires
+    /// only comes out of it as 00, 01 or 11, and only those inputs are tried, 64 at a time.
+    /// It fails with one of the inputs that are left unsorted.
+    /// Networks with more than 32 wires are not validated.
+    ///
+    /// ```rust
+    /// use buldak::network::Network;
+    ///
+    /// assert!(Network::smallest_available(8).validate().is_ok());
+    /// assert!(Network::new(3, vec![(0, 1), (1, 2)]).unwrap().validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), String> {
+        let n = self.size;
+        if n > MAX_VALIDATION_SIZE {
+            return Err(format!(
+                "Networks with more than {} wires can not be validated.",
+                MAX_VALIDATION_SIZE
+            ));
+        }
+
+        // the first layer, and the comparators after it in their order
+        let mut touched = vec![false; n];
+        let mut first = vec![];
+        let mut rest = vec![];
+        for &(i, j) in self.comparators.iter() {
+            if touched[i] || touched[j] {
+                rest.push((i, j));
+            } else {
+                first.push((i, j));
+            }
+            touched[i] = true;
+            touched[j] = true;
+        }
+
+        // An input is a digit for each pair of the first layer, and one for each other wire.
+        let mut digits: Vec<_> = first.iter().map(|&(i, j)| _Digit::Pair(i, j)).collect();
+        let mut paired = vec![false; n];
+        for &(i, j) in first.iter() {
+            paired[i] = true;
+            paired[j] = true;
+        }
+        digits.extend((0..n).filter(|&w| !paired[w]).map(_Digit::Wire));
+
+        // the first digits are spread over the lanes of a u64, the others are counted
+        let mut lanes = 1;
+        let mut lane_digits = 0;
+        while lane_digits < digits.len() && lanes * digits[lane_digits].radix() <= 64 {
+            lanes *= digits[lane_digits].radix();
+            lane_digits += 1;
+        }
+        let lane_mask = if lanes == 64 { !0 } else { (1u64 << lanes) - 1 };
+
+        let mut lane_wires = vec![0u64; n];
+        for lane in 0..lanes {
+            let mut value = lane;
+            for digit in digits[..lane_digits].iter() {
+                digit.set(&mut lane_wires, 1 << lane, value % digit.radix());
+                value /= digit.radix();
+            }
+        }
+
+        let mut counter = vec![0; digits.len() - lane_digits];
+        let mut wires = vec![0u64; n];
+        loop {
+            wires.copy_from_slice(&lane_wires);
+            for (digit, &value) in digits[lane_digits..].iter().zip(counter.iter()) {
+                digit.set(&mut wires, !0, value);
+            }
+
+            for &(i, j) in rest.iter() {
+                let (a, b) = (wires[i], wires[j]);
+                wires[i] = a & b;
+                wires[j] = a | b;
+            }
+
+            let mut unsorted = 0;
+            for w in 1..n {
+                unsorted |= wires[w - 1] & !wires[w];
+            }
+            unsorted &= lane_mask;
+
+            if unsorted != 0 {
+                let lane = unsorted.trailing_zeros() as usize;
+                let mut input = vec![0u64; n];
+                let mut value = lane;
+                for digit in digits[..lane_digits].iter() {
+                    digit.set(&mut input, 1, value % digit.radix());
+                    value /= digit.radix();
+                }
+                for (digit, &value) in digits[lane_digits..].iter().zip(counter.iter()) {
+                    digit.set(&mut input, 1, value);
+                }
+                let input: Vec<u8> = input.into_iter().map(|bit| bit as u8).collect();
+                return Err(format!("The network does not sort {:?}.", input));
+            }
+
+            // the next value of the mixed radix counter, or done if it wraps around
+            let mut k = 0;
+            loop {
+                if k == counter.len() {
+                    return Ok(());
+                }
+                counter[k] += 1;
+                if counter[k] < digits[lane_digits + k].radix() {
+                    break;
+                }
+                counter[k] = 0;
+                k += 1;
+            }
+        }
+    }
+
+    /// Whether [`Network::validate`] succeeds.
+    pub fn is_sorting_network(&self) -> bool {
+        self.validate().is_ok()
+    }
+
+    // Removes `low` wires from the bottom as -∞ and keeps `size` wires above them.
+    // The wires above those are +∞. Comparators touching either are no-ops.
+    fn _prune(&self, low: usize, size: usize) -> Network {
+        let comparators = self
+            .comparators
+            .iter()
+            .filter(|&&(i, j)| i >= low && j < low + size)
+            .map(|&(i, j)| (i - low, j - low))
+            .collect();
+
+        Network { size, comparators }
+    }
+}
+
+/// Sort in ascending order using a sorting network for N.
+///
+/// N up to 32 gets the network of [`Network::smallest_available`].
+/// These networks are built together on the first sort and shared by all threads,
+/// so a sort only looks them up by index. Larger N runs Batcher's merge exchange
+/// stage by stage, like [`crate::oddeven_merge`], so nothing is built or kept for it.
+///
+/// ```rust
+/// use buldak::network;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// network::sort(&mut nums);
+/// assert_eq!(nums, [1, 2, 3, 4, 5, 13, 21, 111, 234]);
+/// ```
+pub fn sort<T, const N: usize>(array: &mut [T; N])
+where
+    T: std::cmp::Ord,
+{
+    sort_by(array, |l, r| l.cmp(r))
+}
+
+/// Sort in descending order using a sorting network for N.
+///
+/// ```rust
+/// use buldak::network;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// network::sort_reverse(&mut nums);
+/// assert_eq!(nums, [234, 111, 21, 13, 5, 4, 3, 2, 1]);
+/// ```
+pub fn sort_reverse<T, const N: usize>(array: &mut [T; N])
+where
+    T: std::cmp::Ord,
+{
+    sort_by(array, |l, r| l.cmp(r).reverse())
+}
+
+/// It takes a comparator function to determine the order,
+/// and sorts it using a sorting network for N.
+///
+/// ```rust
+/// use buldak::network;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// network::sort_by(&mut nums, |l, r| l.cmp(r));
+/// assert_eq!(nums, [1, 2, 3, 4, 5, 13, 21, 111, 234]);
+/// ```
+pub fn sort_by<T, F, const N: usize>(array: &mut [T; N], compare: F)
+where
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    _network_sort_impl(array, compare, <[T]>::swap)
+}
+
+// sorts a slice of any length with the network from the table, or the merge exchange
+pub(crate) fn _network_sort_impl<T, F, S>(array: &mut [T], compare: F, swap: S)
+where
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+    S: FnMut(&mut [T], usize, usize),
+{
+    if array.len() <= MAX_TABLE_SIZE {
+        let comparators = _network_table()[array.len()].comparators();
+        _apply_comparators(comparators, array, compare, swap)
+    } else {
+        crate::oddeven_merge::_oddeven_merge_sort_impl(array, compare, swap)
+    }
+}
+
+// The layers of Batcher's merge exchange (Knuth's Algorithm 5.2.2M), without building them:
+// each stage (p, r, d) compares i with i + d for every i < size - d with i & p == r,
+// and the comparators of a stage touch disjoint wires.
+pub(crate) fn _merge_exchange_stages(size: usize) -> Vec<(usize, usize, usize)> {
+    let mut stages = vec![];
+
+    if size < 2 {
+        return stages;
+    }
+
+    let t = usize::BITS - (size - 1).leading_zeros();
+    let mut p = 1 << (t - 1);
+
+    while p > 0 {
+        let mut q = 1 << (t - 1);
+        let mut r = 0;
+        let mut d = p;
+
+        loop {
+            stages.push((p, r, d));
+
+            if q == p {
+                break;
+            }
+            d = q - p;
+            q /= 2;
+            r = p;
+        }
+
+        p /= 2;
+    }
+
+    stages
+}
+
+static NETWORK_TABLE: OnceLock<Vec<Network>> = OnceLock::new();
+
+fn _network_table() -> &'static [Network] {
+    NETWORK_TABLE.get_or_init(|| {
+        (0..=MAX_TABLE_SIZE)
+            .map(Network::smallest_available)
+            .collect()
+    })
+}
+
+fn _apply_comparators<T, F, S>(
+    comparators: &[(usize, usize)],
+    array: &mut [T],
+    compare: F,
+    mut swap: S,
+) where
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+    S: FnMut(&mut [T], usize, usize),
+{
+    for &(i, j) in comparators {
+        if compare(&array[i], &array[j]) == std::cmp::Ordering::Greater {
+            swap(array, i, j);
+        }
+    }
+}
+
+// A part of a 0-1 input to validate: a wire that is 0 or 1,
+// or the pair of wires of a first layer comparator that is 00, 01 or 11.
+enum _Digit {
+    Wire(usize),
+    Pair(usize, usize),
+}
+
+impl _Digit {
+    fn radix(&self) -> usize {
+        match self {
+            _Digit::Wire(_) => 2,
+            _Digit::Pair(_, _) => 3,
+        }
+    }
+
+    // sets the wires of the digit to the value on the given lanes
+    fn set(&self, wires: &mut [u64], lanes: u64, value: usize) {
+        match *self {
+            _Digit::Wire(i) => {
+                if value == 1 {
+                    wires[i] |= lanes;
+                }
+            }
+            _Digit::Pair(i, j) => {
+                if value == 2 {
+                    wires[i] |= lanes;
+                }
+                if value >= 1 {
+                    wires[j] |= lanes;
+                }
+            }
+        }
+    }
+}
+
+fn _pairwise_pass(
+    comparators: &mut Vec<(usize, usize)>,
+    size: usize,
+    a: usize,
+    d: usize,
+    start: usize,
+) {
+    let mut b = start;
+    let mut c = 0;
+
+    while b < size {
+        comparators.push((b - d * a, b));
+        b += 1;
+        c += 1;
+        if c >= a {
+            c = 0;
+            b += a;
+        }
+    }
+}
+
+fn _tables() -> Vec<Network> {
+    vec![
+        Network {
+            size: 9,
+            comparators: OPTIMAL_9.to_vec(),
+        },
+        Network {
+            size: 10,
+            comparators: OPTIMAL_10.to_vec(),
+        },
+        Network {
+            size: 12,
+            comparators: OPTIMAL_12.to_vec(),
+        },
+        Network {
+            size: 13,
+            comparators: BEST_KNOWN_13.to_vec(),
+        },
+        Network::green16(),
+        Network {
+            size: 17,
+            comparators: SEARCHED_17.to_vec(),
+        },
+        Network {
+            size: 18,
+            comparators: SEARCHED_18.to_vec(),
+        },
+        Network {
+            size: 20,
+            comparators: SEARCHED_20.to_vec(),
+        },
+    ]
+}
+
+// Sorts the first wires with `low`, the rest with `high`,
+// then merges them with Batcher's odd-even merge pruned from twice the size of `low`.
+fn _merge_two(low: &Network, high: &Network) -> Network {
+    let half = low.size;
+    let size = low.size + high.size;
+
+    let mut comparators = low.comparators.clone();
+    comparators.extend(high.comparators.iter().map(|&(i, j)| (i + half, j + half)));
+
+    let mut merge = vec![];
+    _odd_even_merge(&mut merge, 0, half * 2, 1);
+    comparators.extend(merge.into_iter().filter(|&(_, j)| j < size));
+
+    Network { size, comparators }
+}
+
+fn _odd_even_merge(comparators: &mut Vec<(usize, usize)>, low: usize, count: usize, step: usize) {
+    let double = step * 2;
+
+    if double < count {
+        _odd_even_merge(comparators, low, count, double);
+        _odd_even_merge(comparators, low + step, count, double);
+
+        let mut i = low + step;
+        while i + step < low + count {
+            comparators.push((i, i + step));
+            i += double;
+        }
+    } else {
+        comparators.push((low, low + step));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn sort_ascending() {
+        let mut actual = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+        super::sort(&mut actual);
+        assert_eq!(actual, [1, 2, 3, 4, 5, 13, 21, 111, 234]);
+
+        // larger than the table
+        let mut actual = [0i64; 100];
+        for (i, e) in actual.iter_mut().enumerate() {
+            *e = (i as i64 * 7919) % 101;
+        }
+        let mut expected = actual;
+        expected.sort();
+        super::sort(&mut actual);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn sort_descending() {
+        let mut actual = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+        super::sort_reverse(&mut actual);
+        assert_eq!(actual, [234, 111, 21, 13, 5, 4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn generated_networks_sort() {
+        use super::Network;
+
+        for size in 0..=16 {
+            assert!(Network::odd_even_merge(size).is_sorting_network());
+            assert!(Network::bitonic(size).is_sorting_network());
+            assert!(Network::pairwise(size).is_sorting_network());
+        }
+        for size in 0..=32 {
+            assert!(Network::smallest_available(size).is_sorting_network());
+        }
+        assert!(Network::green16().is_sorting_network());
+    }
+}
@@ -57,13 +57,89 @@ where
     T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering,
 {
-    _oddeven_sort_impl(array, compare)
+    _oddeven_sort_impl(array, compare, utils::swap)
 }
 
-fn _oddeven_sort_impl<T, F>(array: &mut [T], compare: F)
+/// Sort in ascending order using a oddeven sort algorithm,
+/// running the compare-exchanges of each phase across worker threads.
+///
+/// ```rust
+/// use buldak::oddeven;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// oddeven::par_sort(&mut nums);
+/// assert_eq!(nums, [1, 2, 3, 4, 5, 13, 21, 111, 234]);
+/// ```
+pub fn par_sort<T>(array: &mut [T])
+where
+    T: std::cmp::Ord + std::marker::Send,
+{
+    par_sort_by(array, |l, r| l.cmp(r))
+}
+
+/// Sort in descending order using a oddeven sort algorithm,
+/// running the compare-exchanges of each phase across worker threads.
+///
+/// ```rust
+/// use buldak::oddeven;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// oddeven::par_sort_reverse(&mut nums);
+/// assert_eq!(nums, [234, 111, 21, 13, 5, 4, 3, 2, 1]);
+/// ```
+pub fn par_sort_reverse<T>(array: &mut [T])
+where
+    T: std::cmp::Ord + std::marker::Send,
+{
+    par_sort_by(array, |l, r| l.cmp(r).reverse())
+}
+
+/// It takes a comparator function to determine the order,
+/// and sorts it using a oddeven sort algorithm,
+/// running the compare-exchanges of each phase across worker threads.
+///
+/// The number of threads is taken from `std::thread::available_parallelism`.
+///
+/// ```rust
+/// use buldak::oddeven;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// oddeven::par_sort_by(&mut nums, |l, r| l.cmp(r));
+/// assert_eq!(nums, [1, 2, 3, 4, 5, 13, 21, 111, 234]);
+/// ```
+pub fn par_sort_by<T, F>(array: &mut [T], compare: F)
+where
+    T: std::cmp::Ord + std::marker::Send,
+    F: Fn(&T, &T) -> std::cmp::Ordering + std::marker::Sync,
+{
+    let threads = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    par_sort_by_with_threads(array, threads, compare)
+}
+
+/// It takes a comparator function to determine the order,
+/// and sorts it using a oddeven sort algorithm with at most `threads` worker threads.
+///
+/// ```rust
+/// use buldak::oddeven;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// oddeven::par_sort_by_with_threads(&mut nums, 3, |l, r| l.cmp(r));
+/// assert_eq!(nums, [1, 2, 3, 4, 5, 13, 21, 111, 234]);
+/// ```
+pub fn par_sort_by_with_threads<T, F>(array: &mut [T], threads: usize, compare: F)
+where
+    T: std::cmp::Ord + std::marker::Send,
+    F: Fn(&T, &T) -> std::cmp::Ordering + std::marker::Sync,
+{
+    _oddeven_par_sort_impl(array, threads, compare)
+}
+
+pub(crate) fn _oddeven_sort_impl<T, F, S>(array: &mut [T], compare: F, mut swap: S)
 where
-    T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering,
+    S: FnMut(&mut [T], usize, usize),
 {
     let mut sorted = false;
 
@@ -73,7 +149,7 @@ where
         let mut i = 1;
         while i < array.len() - 1 {
             if compare(&array[i], &array[i + 1]) == std::cmp::Ordering::Greater {
-                utils::swap(array, i, i + 1);
+                swap(array, i, i + 1);
                 sorted = false;
             }
             i += 2;
@@ -82,7 +158,7 @@ where
         let mut i = 0;
         while i < array.len() - 1 {
             if compare(&array[i], &array[i + 1]) == std::cmp::Ordering::Greater {
-                utils::swap(array, i, i + 1);
+                swap(array, i, i + 1);
                 sorted = false;
             }
             i += 2;
@@ -90,6 +166,108 @@ where
     }
 }
 
+// Each worker owns an even-length chunk, so every even pair and every odd pair
+// except the one across a chunk boundary is local to a single worker.
+// Boundary pairs are handled after a barrier by the worker on their left.
+fn _oddeven_par_sort_impl<T, F>(array: &mut [T], threads: usize, compare: F)
+where
+    T: std::cmp::Ord + std::marker::Send,
+    F: Fn(&T, &T) -> std::cmp::Ordering + std::marker::Sync,
+{
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Barrier, Mutex};
+
+    let workers = std::cmp::max(std::cmp::min(threads, array.len() / 2), 1);
+    let chunk_len = array.len() / workers / 2 * 2;
+
+    let mut chunks = vec![];
+    let mut rest = array;
+    for _ in 1..workers {
+        let (chunk, tail) = rest.split_at_mut(chunk_len);
+        chunks.push(Mutex::new(chunk));
+        rest = tail;
+    }
+    chunks.push(Mutex::new(rest));
+
+    let barrier = Barrier::new(workers);
+    // indexed by round parity, so one round can be reset while the other is read
+    let swapped = [AtomicBool::new(false), AtomicBool::new(false)];
+
+    let compare_exchange = |chunk: &mut [T], i: usize| -> bool {
+        if compare(&chunk[i], &chunk[i + 1]) == std::cmp::Ordering::Greater {
+            utils::swap(chunk, i, i + 1);
+            true
+        } else {
+            false
+        }
+    };
+
+    let worker = |w: usize| {
+        let mut round = 0;
+
+        loop {
+            let flag = &swapped[round % 2];
+            let mut changed = false;
+
+            // even phase
+            {
+                let mut chunk = chunks[w].lock().unwrap();
+                let mut i = 0;
+                while i + 1 < chunk.len() {
+                    changed |= compare_exchange(&mut chunk, i);
+                    i += 2;
+                }
+            }
+
+            if barrier.wait().is_leader() {
+                swapped[(round + 1) % 2].store(false, Ordering::SeqCst);
+            }
+
+            // odd phase, inside the chunk
+            {
+                let mut chunk = chunks[w].lock().unwrap();
+                let mut i = 1;
+                while i + 1 < chunk.len() {
+                    changed |= compare_exchange(&mut chunk, i);
+                    i += 2;
+                }
+            }
+
+            barrier.wait();
+
+            // odd phase, across the boundary with the next chunk
+            if w + 1 < workers {
+                let mut left = chunks[w].lock().unwrap();
+                let mut right = chunks[w + 1].lock().unwrap();
+                let last = left.len() - 1;
+                if compare(&left[last], &right[0]) == std::cmp::Ordering::Greater {
+                    std::mem::swap(&mut left[last], &mut right[0]);
+                    changed = true;
+                }
+            }
+
+            if changed {
+                flag.store(true, Ordering::SeqCst);
+            }
+
+            barrier.wait();
+
+            if !flag.load(Ordering::SeqCst) {
+                break;
+            }
+            round += 1;
+        }
+    };
+
+    std::thread::scope(|scope| {
+        for w in 1..workers {
+            let worker = &worker;
+            scope.spawn(move || worker(w));
+        }
+        worker(0);
+    });
+}
+
 mod tests {
     #[test]
     fn sort_ascending() {
@@ -128,4 +306,18 @@ mod tests {
             assert_eq!(actual, case.expected);
         }
     }
+
+    #[test]
+    fn par_sort_ascending() {
+        let input: Vec<i32> = (0..500).map(|i| (i * 7919) % 113).collect();
+
+        let mut expected = input.clone();
+        expected.sort();
+
+        for threads in [1, 2, 3, 8] {
+            let mut actual = input.clone();
+            super::par_sort_by_with_threads(&mut actual, threads, |l, r| l.cmp(r));
+            assert_eq!(actual, expected);
+        }
+    }
 }
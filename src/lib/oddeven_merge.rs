@@ -0,0 +1,295 @@
+//! odd-even merge sort algorithm.
+//!
+//! Batcher's sorting network, in the merge exchange form that works for any length.
+//! It runs the stages of [`crate::network::Network::odd_even_merge`] without building its comparators.
+//!
+//! unstable sort
+//! **O(Nlog²₂N)**
+
+mod utils;
+
+/// Below this many comparators, a stage is run on the current thread.
+const PARALLEL_THRESHOLD: usize = 2048;
+
+/// Sort in ascending order using a odd-even merge sort algorithm.
+///
+/// ```rust
+/// use buldak::oddeven_merge;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// oddeven_merge::sort(&mut nums);
+/// assert_eq!(nums, [1, 2, 3, 4, 5, 13, 21, 111, 234]);
+/// ```
+pub fn sort<T>(array: &mut [T])
+where
+    T: std::cmp::Ord,
+{
+    sort_by(array, |l, r| l.cmp(r))
+}
+
+/// Sort in descending order using a odd-even merge sort algorithm.
+///
+/// ```rust
+/// use buldak::oddeven_merge;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// oddeven_merge::sort_reverse(&mut nums);
+/// assert_eq!(nums, [234, 111, 21, 13, 5, 4, 3, 2, 1]);
+/// ```
+pub fn sort_reverse<T>(array: &mut [T])
+where
+    T: std::cmp::Ord,
+{
+    sort_by(array, |l, r| l.cmp(r).reverse())
+}
+
+/// It takes a comparator function to determine the order,
+/// and sorts it using a odd-even merge sort algorithm.
+///
+/// ```rust
+/// use buldak::oddeven_merge;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// oddeven_merge::sort_by(&mut nums, |l, r| l.cmp(r));
+/// assert_eq!(nums, [1, 2, 3, 4, 5, 13, 21, 111, 234]);
+/// ```
+pub fn sort_by<T, F>(array: &mut [T], compare: F)
+where
+    T: std::cmp::Ord,
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    _oddeven_merge_sort_impl(array, compare, utils::swap)
+}
+
+/// Sort in ascending order using a odd-even merge sort algorithm,
+/// running the comparisons of each stage across threads.
+///
+/// ```rust
+/// use buldak::oddeven_merge;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// oddeven_merge::par_sort(&mut nums);
+/// assert_eq!(nums, [1, 2, 3, 4, 5, 13, 21, 111, 234]);
+/// ```
+pub fn par_sort<T>(array: &mut [T])
+where
+    T: std::cmp::Ord + std::marker::Sync,
+{
+    par_sort_by(array, |l, r| l.cmp(r))
+}
+
+/// Sort in descending order using a odd-even merge sort algorithm,
+/// running the comparisons of each stage across threads.
+///
+/// ```rust
+/// use buldak::oddeven_merge;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// oddeven_merge::par_sort_reverse(&mut nums);
+/// assert_eq!(nums, [234, 111, 21, 13, 5, 4, 3, 2, 1]);
+/// ```
+pub fn par_sort_reverse<T>(array: &mut [T])
+where
+    T: std::cmp::Ord + std::marker::Sync,
+{
+    par_sort_by(array, |l, r| l.cmp(r).reverse())
+}
+
+/// It takes a comparator function to determine the order,
+/// and sorts it using a odd-even merge sort algorithm,
+/// running the comparisons of each stage across threads.
+///
+/// The number of threads is taken from `std::thread::available_parallelism`.
+///
+/// ```rust
+/// use buldak::oddeven_merge;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// oddeven_merge::par_sort_by(&mut nums, |l, r| l.cmp(r));
+/// assert_eq!(nums, [1, 2, 3, 4, 5, 13, 21, 111, 234]);
+/// ```
+pub fn par_sort_by<T, F>(array: &mut [T], compare: F)
+where
+    T: std::cmp::Ord + std::marker::Sync,
+    F: Fn(&T, &T) -> std::cmp::Ordering + std::marker::Sync,
+{
+    let threads = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    par_sort_by_with_threads(array, threads, compare)
+}
+
+/// It takes a comparator function to determine the order,
+/// and sorts it using a odd-even merge sort algorithm with at most `threads` threads.
+///
+/// ```rust
+/// use buldak::oddeven_merge;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// oddeven_merge::par_sort_by_with_threads(&mut nums, 3, |l, r| l.cmp(r));
+/// assert_eq!(nums, [1, 2, 3, 4, 5, 13, 21, 111, 234]);
+/// ```
+pub fn par_sort_by_with_threads<T, F>(array: &mut [T], threads: usize, compare: F)
+where
+    T: std::cmp::Ord + std::marker::Sync,
+    F: Fn(&T, &T) -> std::cmp::Ordering + std::marker::Sync,
+{
+    _oddeven_merge_par_sort_impl(array, std::cmp::max(threads, 1), compare)
+}
+
+/// The number of comparators used to sort `len` elements.
+///
+/// ```rust
+/// use buldak::{bitonic, oddeven_merge};
+///
+/// assert_eq!(oddeven_merge::comparator_count(16), 63);
+/// assert!(oddeven_merge::comparator_count(1000) < bitonic::comparator_count(1000));
+/// ```
+pub fn comparator_count(len: usize) -> usize {
+    crate::network::_merge_exchange_stages(len)
+        .into_iter()
+        .map(|(p, r, d)| {
+            let count = len - d;
+            let blocks = count / (p * 2) * p;
+            let rest = count % (p * 2);
+
+            blocks
+                + if r == 0 {
+                    std::cmp::min(rest, p)
+                } else {
+                    rest.saturating_sub(p)
+                }
+        })
+        .sum()
+}
+
+pub(crate) fn _oddeven_merge_sort_impl<T, F, S>(array: &mut [T], compare: F, mut swap: S)
+where
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+    S: FnMut(&mut [T], usize, usize),
+{
+    for (p, r, d) in crate::network::_merge_exchange_stages(array.len()) {
+        for i in 0..(array.len() - d) {
+            if i & p == r && compare(&array[i], &array[i + d]) == std::cmp::Ordering::Greater {
+                swap(array, i, i + d);
+            }
+        }
+    }
+}
+
+// The comparisons of a stage are split across threads, which only read the array.
+// The exchanges are then applied on the current thread before the next stage starts.
+fn _oddeven_merge_par_sort_impl<T, F>(array: &mut [T], threads: usize, compare: F)
+where
+    T: std::cmp::Ord + std::marker::Sync,
+    F: Fn(&T, &T) -> std::cmp::Ordering + std::marker::Sync,
+{
+    for (p, r, d) in crate::network::_merge_exchange_stages(array.len()) {
+        let count = array.len() - d;
+
+        if threads <= 1 || count < PARALLEL_THRESHOLD {
+            for i in 0..count {
+                if i & p == r && compare(&array[i], &array[i + d]) == std::cmp::Ordering::Greater {
+                    utils::swap(array, i, i + d);
+                }
+            }
+            continue;
+        }
+
+        let chunk = count.div_ceil(threads);
+        let exchanges: Vec<Vec<usize>> = {
+            let array: &[T] = array;
+            let compare = &compare;
+
+            std::thread::scope(|scope| {
+                let handles: Vec<_> = (0..count)
+                    .step_by(chunk)
+                    .map(|begin| {
+                        let end = std::cmp::min(begin + chunk, count);
+                        scope.spawn(move || {
+                            (begin..end)
+                                .filter(|&i| {
+                                    i & p == r
+                                        && compare(&array[i], &array[i + d])
+                                            == std::cmp::Ordering::Greater
+                                })
+                                .collect::<Vec<usize>>()
+                        })
+                    })
+                    .collect();
+
+                handles
+                    .into_iter()
+                    .map(|handle| handle.join().unwrap())
+                    .collect()
+            })
+        };
+
+        for i in exchanges.into_iter().flatten() {
+            utils::swap(array, i, i + d);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn sort_ascending() {
+        struct TestCase {
+            input: Vec<i32>,
+            expected: Vec<i32>,
+        }
+
+        let test_cases = vec![TestCase {
+            input: vec![1, 4, 2, 3, 5, 111, 234, 21, 13],
+            expected: vec![1, 2, 3, 4, 5, 13, 21, 111, 234],
+        }];
+
+        for case in test_cases {
+            let mut actual = case.input.clone();
+            super::sort(&mut actual);
+            assert_eq!(actual, case.expected);
+        }
+    }
+
+    #[test]
+    fn sort_descending() {
+        struct TestCase {
+            input: Vec<i32>,
+            expected: Vec<i32>,
+        }
+
+        let test_cases = vec![TestCase {
+            input: vec![1, 4, 2, 3, 5, 111, 234, 21, 13],
+            expected: vec![234, 111, 21, 13, 5, 4, 3, 2, 1],
+        }];
+
+        for case in test_cases {
+            let mut actual = case.input.clone();
+            super::sort_reverse(&mut actual);
+            assert_eq!(actual, case.expected);
+        }
+    }
+
+    #[test]
+    fn comparator_count() {
+        for len in 0..200 {
+            let network = crate::network::Network::odd_even_merge(len);
+            assert_eq!(super::comparator_count(len), network.len());
+        }
+    }
+
+    #[test]
+    fn par_sort_ascending() {
+        let input: Vec<i32> = (0..10_000).map(|i| (i * 7919) % 1013).collect();
+
+        let mut expected = input.clone();
+        expected.sort();
+
+        for threads in [1, 2, 3, 8] {
+            let mut actual = input.clone();
+            super::par_sort_by_with_threads(&mut actual, threads, |l, r| l.cmp(r));
+            assert_eq!(actual, expected);
+        }
+    }
+}
@@ -51,11 +51,209 @@ where
 /// ```
 ///
 pub fn sort_by<T, F>(array: &mut [T], compare: F)
+where
+    F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
+{
+    _pancake_sort_impl(array, compare, utils::swap);
+}
+
+/// The longest array that [`minimal_flip_sequence`] searches through.
+pub const MAX_MINIMAL_LEN: usize = 9;
+
+/// Returns the lengths of the prefixes that a pancake sort reverses
+/// to sort it in ascending order, without changing the array.
+///
+/// ```rust
+/// use buldak::pancake;
+///
+/// let mut nums = [3, 1, 2];
+/// let flips = pancake::flip_sequence(&nums);
+/// assert_eq!(flips, vec![3, 2]);
+///
+/// pancake::apply_flips(&mut nums, &flips).unwrap();
+/// assert_eq!(nums, [1, 2, 3]);
+/// ```
+pub fn flip_sequence<T>(array: &[T]) -> Vec<usize>
 where
     T: std::cmp::Ord,
+{
+    flip_sequence_by(array, |l, r| l.cmp(r))
+}
+
+/// It takes a comparator function to determine the order,
+/// and returns the lengths of the prefixes that a pancake sort reverses to sort it.
+///
+/// ```rust
+/// use buldak::pancake;
+///
+/// let flips = pancake::flip_sequence_by(&[3, 1, 2], |l, r| l.cmp(r).reverse());
+/// assert_eq!(flips, vec![2, 3, 2]);
+/// ```
+pub fn flip_sequence_by<T, F>(array: &[T], compare: F) -> Vec<usize>
+where
     F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
 {
-    _pancake_sort_impl(array, compare)
+    let mut order: Vec<&T> = array.iter().collect();
+    _pancake_sort_impl(&mut order, |l: &&T, r: &&T| compare(l, r), utils::swap)
+}
+
+/// Returns the fewest prefix reversals that sort it in ascending order,
+/// found by a breadth-first search over the orderings of the array.
+///
+/// It fails if the array is longer than [`MAX_MINIMAL_LEN`].
+///
+/// ```rust
+/// use buldak::pancake;
+///
+/// let nums = [3, 5, 4, 2, 1];
+/// assert_eq!(pancake::flip_sequence(&nums).len(), 6);
+/// assert_eq!(pancake::minimal_flip_sequence(&nums).unwrap().len(), 3);
+/// ```
+pub fn minimal_flip_sequence<T>(array: &[T]) -> Result<Vec<usize>, String>
+where
+    T: std::cmp::Ord,
+{
+    minimal_flip_sequence_by(array, |l, r| l.cmp(r))
+}
+
+/// It takes a comparator function to determine the order,
+/// and returns the fewest prefix reversals that sort it.
+///
+/// ```rust
+/// use buldak::pancake;
+///
+/// let flips = pancake::minimal_flip_sequence_by(&[3, 1, 2], |l, r| l.cmp(r).reverse());
+/// assert_eq!(flips.unwrap(), vec![2, 3, 2]);
+/// ```
+pub fn minimal_flip_sequence_by<T, F>(array: &[T], compare: F) -> Result<Vec<usize>, String>
+where
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    if array.len() > MAX_MINIMAL_LEN {
+        return Err(format!(
+            "The array is too long to search for the fewest flips. It must be at most {} long.",
+            MAX_MINIMAL_LEN
+        ));
+    }
+
+    // equal elements get the same rank, so any of their orders is sorted
+    let ranks: Vec<usize> = array
+        .iter()
+        .map(|e| {
+            array
+                .iter()
+                .filter(|other| compare(other, e) == std::cmp::Ordering::Less)
+                .count()
+        })
+        .collect();
+
+    let mut target = ranks.clone();
+    target.sort_unstable();
+
+    Ok(_minimal_flips(ranks, target))
+}
+
+/// Applies the prefix reversals returned by [`flip_sequence`] in order.
+///
+/// It fails without changing the array if a flip is longer than the array.
+///
+/// ```rust
+/// use buldak::pancake;
+///
+/// let mut nums = [1, 2, 3, 4];
+/// pancake::apply_flips(&mut nums, &[2, 4]).unwrap();
+/// assert_eq!(nums, [4, 3, 1, 2]);
+///
+/// assert!(pancake::apply_flips(&mut nums, &[5]).is_err());
+/// ```
+pub fn apply_flips<T>(array: &mut [T], flips: &[usize]) -> Result<(), String> {
+    _check_flips(array.len(), flips)?;
+
+    for &flip in flips {
+        array[..flip].reverse();
+    }
+
+    Ok(())
+}
+
+/// Sort in ascending order of the absolute values using a burnt pancake sort algorithm,
+/// and returns the lengths of the reversed prefixes.
+///
+/// Every flip also turns the flipped pancakes over, which negates them.
+/// When it's done, every pancake has its burnt side down, that is, is positive.
+///
+/// Every element must have a negation. A value like `i32::MIN` can't be turned over:
+/// flipping it panics when overflow checks are on, and leaves it negative otherwise.
+///
+/// ```rust
+/// use buldak::pancake;
+///
+/// let mut nums = [-3, 1, -2];
+/// let flips = pancake::burnt_sort(&mut nums);
+/// assert_eq!(nums, [1, 2, 3]);
+///
+/// let mut again = [-3, 1, -2];
+/// pancake::apply_burnt_flips(&mut again, &flips).unwrap();
+/// assert_eq!(again, nums);
+/// ```
+pub fn burnt_sort<T>(array: &mut [T]) -> Vec<usize>
+where
+    T: std::cmp::Ord + std::ops::Neg<Output = T> + std::default::Default + std::clone::Clone,
+{
+    _burnt_pancake_sort_impl(array)
+}
+
+/// Returns the lengths of the prefixes that [`burnt_sort`] flips, without changing the array.
+///
+/// Like [`burnt_sort`], it can't turn over a value without a negation, such as `i32::MIN`.
+///
+/// ```rust
+/// use buldak::pancake;
+///
+/// assert_eq!(pancake::burnt_flip_sequence(&[1, 2, 3]), vec![]);
+/// assert_eq!(pancake::burnt_flip_sequence(&[1, -2, 3]), vec![2, 1, 2]);
+/// ```
+pub fn burnt_flip_sequence<T>(array: &[T]) -> Vec<usize>
+where
+    T: std::cmp::Ord + std::ops::Neg<Output = T> + std::default::Default + std::clone::Clone,
+{
+    _burnt_pancake_sort_impl(&mut array.to_vec())
+}
+
+/// Applies the flips returned by [`burnt_sort`] in order,
+/// negating every flipped element.
+///
+/// It fails without changing the array if a flip is longer than the array.
+/// Like [`burnt_sort`], it can't turn over a value without a negation, such as `i32::MIN`.
+///
+/// ```rust
+/// use buldak::pancake;
+///
+/// let mut nums = [1, 2, 3];
+/// pancake::apply_burnt_flips(&mut nums, &[2]).unwrap();
+/// assert_eq!(nums, [-2, -1, 3]);
+/// ```
+pub fn apply_burnt_flips<T>(array: &mut [T], flips: &[usize]) -> Result<(), String>
+where
+    T: std::ops::Neg<Output = T> + std::clone::Clone,
+{
+    _check_flips(array.len(), flips)?;
+
+    for &flip in flips {
+        _burnt_flip(array, flip);
+    }
+
+    Ok(())
+}
+
+fn _check_flips(len: usize, flips: &[usize]) -> Result<(), String> {
+    match flips.iter().find(|&&flip| flip > len) {
+        Some(flip) => Err(format!(
+            "The flip of {} pancakes is longer than the array of {}.",
+            flip, len
+        )),
+        None => Ok(()),
+    }
 }
 
 // Returns index of the
@@ -63,7 +261,6 @@ where
 // arr[0..n-1]
 fn _find_max<T, F>(array: &[T], n: usize, compare: F) -> usize
 where
-    T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering,
 {
     let mut max_index = 0;
@@ -76,20 +273,26 @@ where
 }
 
 // Reverses arr[0..i]
-fn _flip<T>(array: &mut [T], mut i: usize) {
+fn _flip<T, S>(array: &mut [T], mut i: usize, swap: &mut S)
+where
+    S: FnMut(&mut [T], usize, usize),
+{
     let mut start = 0;
     while start < i {
-        utils::swap(array, start, i);
+        swap(array, start, i);
         start += 1;
         i -= 1;
     }
 }
 
-fn _pancake_sort_impl<T, F>(array: &mut [T], compare: F)
+// Returns the lengths of the prefixes it reversed.
+pub(crate) fn _pancake_sort_impl<T, F, S>(array: &mut [T], compare: F, mut swap: S) -> Vec<usize>
 where
-    T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
+    S: FnMut(&mut [T], usize, usize),
 {
+    let mut flips = vec![];
+
     // Start from the complete
     // array and one by one
     // reduce current size by one
@@ -108,14 +311,123 @@ where
             // To move at the end,
             // first move maximum
             // number to beginning
-            _flip(array, max_index);
+            if max_index > 0 {
+                _flip(array, max_index, &mut swap);
+                flips.push(max_index + 1);
+            }
 
             // Now move the maximum
             // number to end by
             // reversing current array
-            _flip(array, i - 1);
+            _flip(array, i - 1, &mut swap);
+            flips.push(i);
         }
     }
+
+    flips
+}
+
+// The states are the ranks packed into 4 bits each,
+// and every flip of 2 or more pancakes is an edge.
+fn _minimal_flips(ranks: Vec<usize>, target: Vec<usize>) -> Vec<usize> {
+    let pack = |ranks: &[usize]| ranks.iter().fold(0u64, |state, &r| (state << 4) | r as u64);
+    let unpack = |mut state: u64, len: usize| {
+        let mut ranks = vec![0; len];
+        for r in ranks.iter_mut().rev() {
+            *r = (state & 0xF) as usize;
+            state >>= 4;
+        }
+        ranks
+    };
+
+    let len = ranks.len();
+    let start = pack(&ranks);
+    let target = pack(&target);
+
+    // the state each state was reached from, and the flip that reached it
+    let mut parents = std::collections::HashMap::new();
+    parents.insert(start, (start, 0));
+    let mut queue = std::collections::VecDeque::new();
+    queue.push_back(start);
+
+    while let Some(state) = queue.pop_front() {
+        if state == target {
+            break;
+        }
+
+        let ranks = unpack(state, len);
+        for flip in 2..=len {
+            let mut next = ranks.clone();
+            next[..flip].reverse();
+            let next = pack(&next);
+
+            if let std::collections::hash_map::Entry::Vacant(entry) = parents.entry(next) {
+                entry.insert((state, flip));
+                queue.push_back(next);
+            }
+        }
+    }
+
+    let mut flips = vec![];
+    let mut state = target;
+    while state != start {
+        let (parent, flip) = parents[&state];
+        flips.push(flip);
+        state = parent;
+    }
+    flips.reverse();
+
+    flips
+}
+
+// Reverses and turns over arr[0..n-1]
+fn _burnt_flip<T>(array: &mut [T], n: usize)
+where
+    T: std::ops::Neg<Output = T> + std::clone::Clone,
+{
+    array[..n].reverse();
+    for e in array[..n].iter_mut() {
+        *e = -e.clone();
+    }
+}
+
+fn _burnt_pancake_sort_impl<T>(array: &mut [T]) -> Vec<usize>
+where
+    T: std::cmp::Ord + std::ops::Neg<Output = T> + std::default::Default + std::clone::Clone,
+{
+    let zero = T::default();
+    let size = |e: &T| {
+        if *e < zero {
+            -e.clone()
+        } else {
+            e.clone()
+        }
+    };
+
+    let mut flips = vec![];
+    let mut flip = |array: &mut [T], n: usize| {
+        _burnt_flip(array, n);
+        flips.push(n);
+    };
+
+    for i in (1..=array.len()).rev() {
+        let max_index = _find_max(array, i, |l, r| size(l).cmp(&size(r)));
+
+        if max_index == i - 1 && array[max_index] >= zero {
+            continue;
+        }
+
+        // bring the largest pancake to the top, burnt side up, then flip it down into place
+        if max_index > 0 {
+            flip(array, max_index + 1);
+        }
+        if array[0] >= zero {
+            flip(array, 1);
+        }
+        flip(array, i);
+    }
+
+    flips
 }
 
 #[cfg(test)]
@@ -157,4 +469,36 @@ mod tests {
             assert_eq!(actual, case.expected);
         }
     }
+
+    #[test]
+    fn flip_sequence() {
+        let input = vec![5, 1, 4, 2, 2, 3];
+        let mut expected = input.clone();
+        expected.sort();
+
+        let flips = super::flip_sequence(&input);
+        assert!(flips.iter().all(|&flip| flip >= 2));
+        let mut actual = input.clone();
+        super::apply_flips(&mut actual, &flips).unwrap();
+        assert_eq!(actual, expected);
+
+        let minimal = super::minimal_flip_sequence(&input).unwrap();
+        assert!(minimal.len() <= flips.len());
+        let mut actual = input.clone();
+        super::apply_flips(&mut actual, &minimal).unwrap();
+        assert_eq!(actual, expected);
+
+        assert_eq!(super::minimal_flip_sequence(&expected).unwrap(), vec![]);
+        assert!(super::minimal_flip_sequence(&[0; 10]).is_err());
+
+        let input = vec![-4, 2, -1, 3, -5];
+        let mut actual = input.clone();
+        let flips = super::burnt_sort(&mut actual);
+        assert_eq!(actual, vec![1, 2, 3, 4, 5]);
+        assert!(flips.len() <= 3 * input.len());
+
+        let mut actual = input;
+        super::apply_burnt_flips(&mut actual, &flips).unwrap();
+        assert_eq!(actual, vec![1, 2, 3, 4, 5]);
+    }
 }
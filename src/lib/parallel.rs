@@ -0,0 +1,522 @@
+//! parallel sort algorithms.
+//!
+//! Multi-threaded sorts built on `std::thread::scope`.
+//! No external runtime is required.
+//!
+//! stable sort (merge), unstable sort (samplesort)
+//! **O(Nlog₂N / P)**: P=number of threads
+
+/// Below this length, a range is sorted on the current thread instead of being split.
+const THRESHOLD: usize = 4096;
+
+/// Below this length, leaves are sorted with insertion sort instead of tim sort.
+const INSERTION_THRESHOLD: usize = 32;
+
+/// Number of sampled elements per bucket when choosing samplesort splitters.
+const OVERSAMPLING: usize = 8;
+
+/// Upper bound on the number of samplesort buckets.
+const MAX_BUCKETS: usize = 256;
+
+/// Sort in ascending order using a parallel merge sort algorithm.
+///
+/// ```rust
+/// use buldak::parallel;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// parallel::sort(&mut nums);
+/// assert_eq!(nums, [1, 2, 3, 4, 5, 13, 21, 111, 234]);
+/// ```
+pub fn sort<T>(array: &mut [T])
+where
+    T: std::cmp::Ord + std::clone::Clone + std::marker::Send + std::marker::Sync,
+{
+    sort_by(array, |l, r| l.cmp(r))
+}
+
+/// Sort in descending order using a parallel merge sort algorithm.
+///
+/// ```rust
+/// use buldak::parallel;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// parallel::sort_reverse(&mut nums);
+/// assert_eq!(nums, [234, 111, 21, 13, 5, 4, 3, 2, 1]);
+/// ```
+pub fn sort_reverse<T>(array: &mut [T])
+where
+    T: std::cmp::Ord + std::clone::Clone + std::marker::Send + std::marker::Sync,
+{
+    sort_by(array, |l, r| l.cmp(r).reverse())
+}
+
+/// It takes a comparator function to determine the order,
+/// and sorts it using a parallel merge sort algorithm.
+///
+/// The number of threads is taken from `std::thread::available_parallelism`.
+///
+/// ```rust
+/// use buldak::parallel;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// parallel::sort_by(&mut nums, |l, r| l.cmp(r));
+/// assert_eq!(nums, [1, 2, 3, 4, 5, 13, 21, 111, 234]);
+/// ```
+pub fn sort_by<T, F>(array: &mut [T], compare: F)
+where
+    T: std::clone::Clone + std::marker::Send + std::marker::Sync,
+    F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone + std::marker::Sync,
+{
+    sort_by_with_threads(array, _available_threads(), compare)
+}
+
+/// It takes a comparator function to determine the order,
+/// and sorts it using a parallel merge sort algorithm with at most `threads` threads.
+///
+/// Passing 0 or 1 sorts on the current thread.
+///
+/// ```rust
+/// use buldak::parallel;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// parallel::sort_by_with_threads(&mut nums, 4, |l, r| l.cmp(r));
+/// assert_eq!(nums, [1, 2, 3, 4, 5, 13, 21, 111, 234]);
+/// ```
+pub fn sort_by_with_threads<T, F>(array: &mut [T], threads: usize, compare: F)
+where
+    T: std::clone::Clone + std::marker::Send + std::marker::Sync,
+    F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone + std::marker::Sync,
+{
+    _parallel_merge_sort_impl(array, std::cmp::max(threads, 1), compare)
+}
+
+/// Sort in ascending order using a parallel samplesort algorithm.
+///
+/// ```rust
+/// use buldak::parallel;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// parallel::sort_unstable(&mut nums);
+/// assert_eq!(nums, [1, 2, 3, 4, 5, 13, 21, 111, 234]);
+/// ```
+pub fn sort_unstable<T>(array: &mut [T])
+where
+    T: std::cmp::Ord + std::clone::Clone + std::marker::Send + std::marker::Sync,
+{
+    sort_unstable_by(array, |l, r| l.cmp(r))
+}
+
+/// Sort in descending order using a parallel samplesort algorithm.
+///
+/// ```rust
+/// use buldak::parallel;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// parallel::sort_unstable_reverse(&mut nums);
+/// assert_eq!(nums, [234, 111, 21, 13, 5, 4, 3, 2, 1]);
+/// ```
+pub fn sort_unstable_reverse<T>(array: &mut [T])
+where
+    T: std::cmp::Ord + std::clone::Clone + std::marker::Send + std::marker::Sync,
+{
+    sort_unstable_by(array, |l, r| l.cmp(r).reverse())
+}
+
+/// It takes a comparator function to determine the order,
+/// and sorts it using a parallel samplesort algorithm.
+///
+/// The number of threads is taken from `std::thread::available_parallelism`.
+/// The order of equal elements is not preserved,
+/// but it is the same for any number of threads.
+///
+/// ```rust
+/// use buldak::parallel;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// parallel::sort_unstable_by(&mut nums, |l, r| l.cmp(r));
+/// assert_eq!(nums, [1, 2, 3, 4, 5, 13, 21, 111, 234]);
+/// ```
+pub fn sort_unstable_by<T, F>(array: &mut [T], compare: F)
+where
+    T: std::clone::Clone + std::marker::Send + std::marker::Sync,
+    F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone + std::marker::Sync,
+{
+    sort_unstable_by_with_threads(array, _available_threads(), compare)
+}
+
+/// It takes a comparator function to determine the order,
+/// and sorts it using a parallel samplesort algorithm with at most `threads` threads.
+///
+/// Passing 0 or 1 sorts on the current thread.
+///
+/// ```rust
+/// use buldak::parallel;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// parallel::sort_unstable_by_with_threads(&mut nums, 4, |l, r| l.cmp(r));
+/// assert_eq!(nums, [1, 2, 3, 4, 5, 13, 21, 111, 234]);
+/// ```
+pub fn sort_unstable_by_with_threads<T, F>(array: &mut [T], threads: usize, compare: F)
+where
+    T: std::clone::Clone + std::marker::Send + std::marker::Sync,
+    F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone + std::marker::Sync,
+{
+    _parallel_sample_sort_impl(array, std::cmp::max(threads, 1), compare)
+}
+
+fn _available_threads() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+fn _parallel_merge_sort_impl<T, F>(array: &mut [T], threads: usize, compare: F)
+where
+    T: std::clone::Clone + std::marker::Send + std::marker::Sync,
+    F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone + std::marker::Sync,
+{
+    if array.len() <= 1 {
+        return;
+    }
+
+    let mut buffer = array.to_vec();
+    _parallel_merge_sort_recursive(array, &mut buffer, threads, &compare);
+}
+
+fn _parallel_merge_sort_recursive<T, F>(
+    array: &mut [T],
+    buffer: &mut [T],
+    threads: usize,
+    compare: &F,
+) where
+    T: std::clone::Clone + std::marker::Send + std::marker::Sync,
+    F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone + std::marker::Sync,
+{
+    if threads <= 1 || array.len() <= THRESHOLD {
+        _sort_leaf(array, compare);
+        return;
+    }
+
+    let middle = array.len() / 2;
+    let left_threads = threads / 2;
+    let right_threads = threads - left_threads;
+
+    {
+        let (array_left, array_right) = array.split_at_mut(middle);
+        let (buffer_left, buffer_right) = buffer.split_at_mut(middle);
+
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                _parallel_merge_sort_recursive(array_left, buffer_left, left_threads, compare)
+            });
+            _parallel_merge_sort_recursive(array_right, buffer_right, right_threads, compare);
+        });
+    }
+
+    {
+        let (array_left, array_right) = array.split_at(middle);
+        _parallel_merge(array_left, array_right, buffer, threads, compare);
+    }
+
+    array.clone_from_slice(buffer);
+}
+
+fn _sort_leaf<T, F>(array: &mut [T], compare: &F)
+where
+    T: std::clone::Clone,
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    if array.len() <= INSERTION_THRESHOLD {
+        crate::insertion::sort_by(array, |l, r| compare(l, r));
+    } else {
+        crate::tim::sort_by(array, |l, r| compare(l, r));
+    }
+}
+
+// Merges two sorted runs into `output`, splitting the output into
+// `threads` equal parts whose boundaries are found by co-ranking.
+fn _parallel_merge<T, F>(left: &[T], right: &[T], output: &mut [T], threads: usize, compare: &F)
+where
+    T: std::clone::Clone + std::marker::Send + std::marker::Sync,
+    F: Fn(&T, &T) -> std::cmp::Ordering + std::marker::Sync,
+{
+    let len = output.len();
+    let threads = std::cmp::min(threads, std::cmp::max(len / INSERTION_THRESHOLD, 1));
+
+    if threads <= 1 {
+        _merge(left, right, output, compare);
+        return;
+    }
+
+    let chunk = len.div_ceil(threads);
+
+    std::thread::scope(|scope| {
+        let mut rest = output;
+        let mut begin = 0;
+        let mut left_begin = 0;
+
+        while begin < len {
+            let end = std::cmp::min(begin + chunk, len);
+            let left_end = _co_rank(end, left, right, compare);
+            let right_begin = begin - left_begin;
+            let right_end = end - left_end;
+
+            let (part, tail) = rest.split_at_mut(end - begin);
+            rest = tail;
+
+            let left_part = &left[left_begin..left_end];
+            let right_part = &right[right_begin..right_end];
+            scope.spawn(move || _merge(left_part, right_part, part, compare));
+
+            begin = end;
+            left_begin = left_end;
+        }
+    });
+}
+
+// Returns how many elements of `left` come before position `k` of the merged output.
+// Ties are resolved in favor of `left`, which keeps the merge stable.
+fn _co_rank<T, F>(k: usize, left: &[T], right: &[T], compare: &F) -> usize
+where
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    let mut low = k.saturating_sub(right.len());
+    let mut high = std::cmp::min(k, left.len());
+
+    while low < high {
+        let i = low + (high - low) / 2;
+        let j = k - i;
+
+        if compare(&left[i], &right[j - 1]) != std::cmp::Ordering::Greater {
+            low = i + 1;
+        } else {
+            high = i;
+        }
+    }
+
+    low
+}
+
+fn _merge<T, F>(left: &[T], right: &[T], output: &mut [T], compare: &F)
+where
+    T: std::clone::Clone,
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    let mut l = 0;
+    let mut r = 0;
+
+    for slot in output.iter_mut() {
+        let take_left = r == right.len()
+            || (l < left.len() && compare(&left[l], &right[r]) != std::cmp::Ordering::Greater);
+
+        if take_left {
+            *slot = left[l].clone();
+            l += 1;
+        } else {
+            *slot = right[r].clone();
+            r += 1;
+        }
+    }
+}
+
+// The bucket layout depends only on the input, never on `threads`,
+// so the result is identical for any number of threads.
+fn _parallel_sample_sort_impl<T, F>(array: &mut [T], threads: usize, compare: F)
+where
+    T: std::clone::Clone + std::marker::Send + std::marker::Sync,
+    F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone + std::marker::Sync,
+{
+    if array.len() <= THRESHOLD {
+        crate::intro::sort_by(array, compare);
+        return;
+    }
+
+    let bucket_count = std::cmp::min(array.len() / THRESHOLD + 1, MAX_BUCKETS);
+    let splitters = _choose_splitters(array, bucket_count, &compare);
+
+    // classify each block into buckets, keeping the original order inside a block
+    let block_len = array.len().div_ceil(threads);
+    let blocks: Vec<Vec<Vec<T>>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = array
+            .chunks(block_len)
+            .map(|block| {
+                let splitters = &splitters;
+                let compare = &compare;
+                scope.spawn(move || _classify(block, splitters, compare))
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    });
+
+    // lay the buckets out in block order and hand them to the workers
+    let mut parts: Vec<Vec<Vec<T>>> = (0..bucket_count).map(|_| vec![]).collect();
+    for block in blocks {
+        for (bucket, values) in block.into_iter().enumerate() {
+            parts[bucket].push(values);
+        }
+    }
+
+    let mut jobs = vec![];
+    let mut rest = array;
+    for part in parts {
+        let len = part.iter().map(|values| values.len()).sum();
+        let (bucket, tail) = rest.split_at_mut(len);
+        rest = tail;
+        jobs.push((bucket, part));
+    }
+
+    // the largest buckets are popped first
+    jobs.sort_by_key(|(bucket, _)| bucket.len());
+    let queue = std::sync::Mutex::new(jobs);
+
+    std::thread::scope(|scope| {
+        for _ in 1..std::cmp::min(threads, bucket_count) {
+            scope.spawn(|| _sort_buckets(&queue, &compare));
+        }
+        _sort_buckets(&queue, &compare);
+    });
+}
+
+// Picks `bucket_count - 1` splitters from a pseudo-random sample with a fixed seed.
+fn _choose_splitters<T, F>(array: &[T], bucket_count: usize, compare: &F) -> Vec<T>
+where
+    T: std::clone::Clone,
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    let mut sample: Vec<T> = (0..bucket_count * OVERSAMPLING)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            array[(state % array.len() as u64) as usize].clone()
+        })
+        .collect();
+
+    crate::intro::sort_by(&mut sample, |l, r| compare(l, r));
+
+    (1..bucket_count)
+        .map(|i| sample[i * OVERSAMPLING - 1].clone())
+        .collect()
+}
+
+fn _classify<T, F>(block: &[T], splitters: &[T], compare: &F) -> Vec<Vec<T>>
+where
+    T: std::clone::Clone,
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    let mut buckets: Vec<Vec<T>> = (0..=splitters.len()).map(|_| vec![]).collect();
+
+    for e in block {
+        let bucket = splitters
+            .partition_point(|splitter| compare(splitter, e) != std::cmp::Ordering::Greater);
+        buckets[bucket].push(e.clone());
+    }
+
+    buckets
+}
+
+// a bucket slice and the classified values to move into it
+type BucketJob<'a, T> = (&'a mut [T], Vec<Vec<T>>);
+
+fn _sort_buckets<T, F>(queue: &std::sync::Mutex<Vec<BucketJob<T>>>, compare: &F)
+where
+    T: std::clone::Clone,
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    loop {
+        let job = queue.lock().unwrap().pop();
+
+        let (bucket, parts) = match job {
+            Some(job) => job,
+            None => return,
+        };
+
+        for (slot, value) in bucket.iter_mut().zip(parts.into_iter().flatten()) {
+            *slot = value;
+        }
+
+        crate::intro::sort_by(bucket, |l, r| compare(l, r));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn sort_ascending() {
+        struct TestCase {
+            input: Vec<i32>,
+            expected: Vec<i32>,
+        }
+
+        let test_cases = vec![TestCase {
+            input: vec![1, 4, 2, 3, 5, 111, 234, 21, 13],
+            expected: vec![1, 2, 3, 4, 5, 13, 21, 111, 234],
+        }];
+
+        for case in test_cases {
+            let mut actual = case.input.clone();
+            super::sort(&mut actual);
+            assert_eq!(actual, case.expected);
+        }
+    }
+
+    #[test]
+    fn sort_descending() {
+        struct TestCase {
+            input: Vec<i32>,
+            expected: Vec<i32>,
+        }
+
+        let test_cases = vec![TestCase {
+            input: vec![1, 4, 2, 3, 5, 111, 234, 21, 13],
+            expected: vec![234, 111, 21, 13, 5, 4, 3, 2, 1],
+        }];
+
+        for case in test_cases {
+            let mut actual = case.input.clone();
+            super::sort_reverse(&mut actual);
+            assert_eq!(actual, case.expected);
+        }
+    }
+
+    #[test]
+    fn sort_unstable_with_threads() {
+        let input: Vec<(i32, usize)> = (0..50_000)
+            .map(|i| ((i * 7919) % 1000, i as usize))
+            .collect();
+
+        let mut expected = input.clone();
+        expected.sort_by_key(|e| e.0);
+
+        let mut first = input.clone();
+        super::sort_unstable_by_with_threads(&mut first, 1, |l, r| l.0.cmp(&r.0));
+        assert!(first.iter().map(|e| e.0).eq(expected.iter().map(|e| e.0)));
+
+        for threads in [2, 3, 8] {
+            let mut actual = input.clone();
+            super::sort_unstable_by_with_threads(&mut actual, threads, |l, r| l.0.cmp(&r.0));
+            assert_eq!(actual, first);
+        }
+    }
+
+    #[test]
+    fn sort_stable_with_threads() {
+        let input: Vec<(i32, usize)> = (0..50_000)
+            .map(|i| ((i * 7919) % 1000, i as usize))
+            .collect();
+
+        let mut expected = input.clone();
+        expected.sort_by_key(|e| e.0);
+
+        for threads in [1, 2, 3, 8] {
+            let mut actual = input.clone();
+            super::sort_by_with_threads(&mut actual, threads, |l, r| l.0.cmp(&r.0));
+            assert_eq!(actual, expected);
+        }
+    }
+}
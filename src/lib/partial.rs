@@ -0,0 +1,247 @@
+//! partial sort and top-k algorithms.
+//!
+//! Only the first k elements are sorted, with a heap of k elements
+//! that keeps the best ones seen so far.
+//!
+//! unstable sort  
+//! **O(Nlog₂K)**
+
+mod utils;
+
+/// Puts the k smallest elements at the front in ascending order using a heap select,
+/// leaving the rest in an unspecified order.
+///
+/// ```rust
+/// use buldak::partial;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// partial::partial_sort(&mut nums, 4);
+/// assert_eq!(nums[..4], [1, 2, 3, 4]);
+/// ```
+pub fn partial_sort<T>(array: &mut [T], k: usize)
+where
+    T: std::cmp::Ord,
+{
+    partial_sort_by(array, k, |l, r| l.cmp(r))
+}
+
+/// It takes a comparator function to determine the order,
+/// and puts the first k elements in that order at the front, sorted, using a heap select.
+///
+/// If k is longer than the array, the whole array is sorted.
+///
+/// ```rust
+/// use buldak::partial;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// partial::partial_sort_by(&mut nums, 3, |l, r| l.cmp(r).reverse());
+/// assert_eq!(nums[..3], [234, 111, 21]);
+/// ```
+pub fn partial_sort_by<T, F>(array: &mut [T], k: usize, compare: F)
+where
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    _partial_sort_impl(array, k, &compare)
+}
+
+/// Returns the k greatest elements of an iterator in descending order,
+/// holding no more than k elements at a time.
+///
+/// ```rust
+/// use buldak::partial;
+///
+/// let top = partial::top_k(vec![1, 4, 2, 3, 5, 111, 234, 21, 13], 3);
+/// assert_eq!(top, vec![234, 111, 21]);
+/// ```
+pub fn top_k<T, I>(iter: I, k: usize) -> Vec<T>
+where
+    T: std::cmp::Ord,
+    I: std::iter::IntoIterator<Item = T>,
+{
+    top_k_by(iter, k, |l, r| l.cmp(r))
+}
+
+/// It takes a comparator function to determine the order,
+/// and returns the k greatest elements of an iterator in that order, greatest first,
+/// holding no more than k elements at a time.
+///
+/// ```rust
+/// use buldak::partial;
+///
+/// let rows = vec![("a", 30), ("b", 10), ("c", 20), ("d", 40)];
+/// let top = partial::top_k_by(rows, 2, |l, r| l.1.cmp(&r.1));
+/// assert_eq!(top, vec![("d", 40), ("a", 30)]);
+/// ```
+pub fn top_k_by<T, I, F>(iter: I, k: usize, compare: F) -> Vec<T>
+where
+    I: std::iter::IntoIterator<Item = T>,
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    let mut top = TopK::new(k, compare);
+    top.extend(iter);
+    top.into_sorted_vec()
+}
+
+/// The k greatest elements of a stream, kept in a bounded heap.
+///
+/// ```rust
+/// use buldak::partial::TopK;
+///
+/// let mut top = TopK::new(2, |l: &i32, r: &i32| l.cmp(r));
+/// for e in [1, 4, 2, 3, 5, 111, 234, 21, 13] {
+///     top.push(e);
+/// }
+/// assert_eq!(top.min(), Some(&111));
+/// assert_eq!(top.into_sorted_vec(), vec![234, 111]);
+/// ```
+pub struct TopK<T, F> {
+    // a heap whose root is the least of the elements kept
+    heap: Vec<T>,
+    k: usize,
+    compare: F,
+}
+
+impl<T, F> TopK<T, F>
+where
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    /// An empty heap that keeps the k greatest elements in the order of `compare`.
+    ///
+    /// Nothing is allocated up front, so k can be larger than the number of elements pushed.
+    pub fn new(k: usize, compare: F) -> Self {
+        TopK {
+            heap: Vec::new(),
+            k,
+            compare,
+        }
+    }
+
+    /// Adds the value, dropping the least element kept if there are more than k.
+    pub fn push(&mut self, value: T) {
+        let compare = &self.compare;
+        let reverse = |l: &T, r: &T| compare(l, r).reverse();
+
+        if self.heap.len() < self.k {
+            let last = self.heap.len();
+            self.heap.push(value);
+            crate::heap::_sift_up(&mut self.heap, last, &reverse);
+        } else if self.k > 0 && compare(&value, &self.heap[0]) == std::cmp::Ordering::Greater {
+            self.heap[0] = value;
+            crate::heap::_sift_down(&mut self.heap, 0, self.k, &reverse);
+        }
+    }
+
+    /// The least element kept, which a new value must be greater than to be kept once it's full.
+    pub fn min(&self) -> Option<&T> {
+        self.heap.first()
+    }
+
+    /// The number of elements kept.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Whether no element is kept.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// The elements kept, greatest first.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let compare = &self.compare;
+        let reverse = |l: &T, r: &T| compare(l, r).reverse();
+
+        for end in (1..self.heap.len()).rev() {
+            utils::swap(&mut self.heap, 0, end);
+            crate::heap::_sift_down(&mut self.heap, 0, end, &reverse);
+        }
+
+        self.heap
+    }
+}
+
+impl<T, F> std::iter::Extend<T> for TopK<T, F>
+where
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: std::iter::IntoIterator<Item = T>,
+    {
+        let iter = iter.into_iter();
+        let room = self.k - self.heap.len();
+        self.heap.reserve(std::cmp::min(room, iter.size_hint().0));
+
+        for e in iter {
+            self.push(e);
+        }
+    }
+}
+
+// The heap of array[..k] has the greatest of the k smallest so far at the root,
+// which every later element smaller than it replaces.
+fn _partial_sort_impl<T, F>(array: &mut [T], k: usize, compare: &F)
+where
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    let k = std::cmp::min(k, array.len());
+    if k == 0 {
+        return;
+    }
+
+    for root in (0..k / 2).rev() {
+        crate::heap::_sift_down(array, root, k, compare);
+    }
+
+    for i in k..array.len() {
+        if compare(&array[i], &array[0]) == std::cmp::Ordering::Less {
+            utils::swap(array, 0, i);
+            crate::heap::_sift_down(array, 0, k, compare);
+        }
+    }
+
+    for end in (1..k).rev() {
+        utils::swap(array, 0, end);
+        crate::heap::_sift_down(array, 0, end, compare);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn partial_sort() {
+        let input: Vec<i64> = (0..5_000).map(|i| (i * 7919) % 1_009).collect();
+        let mut expected = input.clone();
+        expected.sort();
+
+        for k in [0, 1, 100, 4_999, 5_000, 10_000] {
+            let mut actual = input.clone();
+            super::partial_sort(&mut actual, k);
+
+            let k = std::cmp::min(k, input.len());
+            assert_eq!(actual[..k], expected[..k]);
+
+            actual.sort();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn top_k() {
+        let input: Vec<i64> = (0..5_000).map(|i| (i * 7919) % 1_009).collect();
+        let mut expected = input.clone();
+        expected.sort_by_key(|&e| std::cmp::Reverse(e));
+
+        for k in [0, 1, 100, 5_000, 10_000, usize::MAX] {
+            let actual = super::top_k(input.iter().copied(), k);
+            assert_eq!(actual, expected[..std::cmp::min(k, input.len())]);
+        }
+
+        let mut top = super::TopK::new(3, |l: &i64, r: &i64| l.cmp(r).reverse());
+        assert!(top.is_empty());
+        top.extend((0..1_000).map(|i| (i * 7) % 1_000));
+        assert_eq!(top.len(), 3);
+        assert_eq!(top.min(), Some(&2));
+        assert_eq!(top.into_sorted_vec(), vec![0, 1, 2]);
+    }
+}
@@ -0,0 +1,230 @@
+//! pigeonhole sort algorithm.
+//!
+//! Every value in the range between the min and the max gets its own hole,
+//! which counts the elements with that value.
+//!
+//! stable sort
+//! **O(N+R)**: R=max-min+1
+
+/// The largest range of values that gets holes allocated for it.
+///
+/// A hole is a single `usize` count, so the largest range takes 128 MiB on 64-bit targets.
+/// Besides the holes, a sort allocates three buffers of the length of the array:
+/// the key of each element, which becomes its hole, the order to place them in,
+/// and the sorted clones.
+pub const MAX_HOLES: usize = 1 << 24;
+
+/// Sort in ascending order using a pigeonhole sort algorithm.
+///
+/// It fails if the values are further apart than [`MAX_HOLES`].
+///
+/// ```rust
+/// use buldak::pigeonhole;
+///
+/// let mut nums = [1, 4, 2, 3, 5, -44, 111, 234, 21, 13];
+/// pigeonhole::sort(&mut nums).unwrap();
+/// assert_eq!(nums, [-44, 1, 2, 3, 4, 5, 13, 21, 111, 234]);
+///
+/// assert!(pigeonhole::sort(&mut [i64::MIN, i64::MAX]).is_err());
+/// ```
+pub fn sort<T>(array: &mut [T]) -> Result<(), String>
+where
+    T: std::convert::TryInto<isize> + std::clone::Clone,
+{
+    _pigeonhole_sort_impl(array, _to_isize, true)
+}
+
+/// Sort in descending order using a pigeonhole sort algorithm.
+///
+/// ```rust
+/// use buldak::pigeonhole;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13, -2];
+/// pigeonhole::sort_reverse(&mut nums).unwrap();
+/// assert_eq!(nums, [234, 111, 21, 13, 5, 4, 3, 2, 1, -2]);
+/// ```
+pub fn sort_reverse<T>(array: &mut [T]) -> Result<(), String>
+where
+    T: std::convert::TryInto<isize> + std::clone::Clone,
+{
+    _pigeonhole_sort_impl(array, _to_isize, false)
+}
+
+/// It takes a function that extracts an integer key from each element,
+/// and sorts it in ascending order of the keys using a pigeonhole sort algorithm.
+///
+/// ```rust
+/// use buldak::pigeonhole;
+///
+/// let mut readings = [(21, "b"), (19, "a"), (21, "a"), (20, "c")];
+/// pigeonhole::sort_by_key(&mut readings, |e| e.0).unwrap();
+/// assert_eq!(readings, [(19, "a"), (20, "c"), (21, "b"), (21, "a")]);
+/// ```
+pub fn sort_by_key<T, F>(array: &mut [T], key: F) -> Result<(), String>
+where
+    T: std::clone::Clone,
+    F: Fn(&T) -> isize,
+{
+    _pigeonhole_sort_impl(array, |e| Ok(key(e)), true)
+}
+
+/// It takes a function that extracts an integer key from each element,
+/// and sorts it in descending order of the keys using a pigeonhole sort algorithm.
+///
+/// ```rust
+/// use buldak::pigeonhole;
+///
+/// let mut readings = [(21, "b"), (19, "a"), (21, "a"), (20, "c")];
+/// pigeonhole::sort_by_key_reverse(&mut readings, |e| e.0).unwrap();
+/// assert_eq!(readings, [(21, "b"), (21, "a"), (20, "c"), (19, "a")]);
+/// ```
+pub fn sort_by_key_reverse<T, F>(array: &mut [T], key: F) -> Result<(), String>
+where
+    T: std::clone::Clone,
+    F: Fn(&T) -> isize,
+{
+    _pigeonhole_sort_impl(array, |e| Ok(key(e)), false)
+}
+
+fn _to_isize<T>(e: &T) -> Result<isize, String>
+where
+    T: std::convert::TryInto<isize> + std::clone::Clone,
+{
+    e.clone().try_into().map_err(|_| {
+        "Conversion to isize failed. All elements of the array must be convertible to isize."
+            .to_string()
+    })
+}
+
+fn _pigeonhole_sort_impl<T, F>(array: &mut [T], key: F, asc: bool) -> Result<(), String>
+where
+    T: std::clone::Clone,
+    F: Fn(&T) -> Result<isize, String>,
+{
+    if array.is_empty() {
+        return Ok(());
+    }
+
+    let mut holes = array
+        .iter()
+        .map(key)
+        .collect::<Result<Vec<isize>, String>>()?;
+    let min = *holes.iter().min().unwrap();
+    let max = *holes.iter().max().unwrap();
+
+    let span = max as i128 - min as i128 + 1;
+    if span > MAX_HOLES as i128 {
+        return Err("The range of the values is too large.".to_string());
+    }
+
+    // each hole is only a count; the counts become the offsets the holes start at.
+    let mut offsets = vec![0usize; span as usize];
+    // the keys become the holes in place, which the span keeps from overflowing
+    for h in holes.iter_mut() {
+        *h -= min;
+        offsets[*h as usize] += 1;
+    }
+
+    let mut start = 0;
+    let mut place = |count: &mut usize| {
+        let next = start + *count;
+        *count = start;
+        start = next;
+    };
+    if asc {
+        offsets.iter_mut().for_each(&mut place);
+    } else {
+        offsets.iter_mut().rev().for_each(&mut place);
+    }
+
+    let mut order = vec![0; array.len()];
+    for (i, &h) in holes.iter().enumerate() {
+        let h = h as usize;
+        order[offsets[h]] = i;
+        offsets[h] += 1;
+    }
+
+    let sorted = order
+        .into_iter()
+        .map(|i| array[i].clone())
+        .collect::<Vec<T>>();
+    array.clone_from_slice(&sorted);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn sort_ascending() {
+        struct TestCase {
+            input: Vec<i32>,
+            expected: Vec<i32>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                input: vec![1, 4, 2, 3, 5, 111, 234, 21, 13],
+                expected: vec![1, 2, 3, 4, 5, 13, 21, 111, 234],
+            },
+            TestCase {
+                input: vec![],
+                expected: vec![],
+            },
+        ];
+
+        for case in test_cases {
+            let mut actual = case.input.clone();
+            super::sort(&mut actual).unwrap();
+            assert_eq!(actual, case.expected);
+        }
+    }
+
+    #[test]
+    fn sort_descending() {
+        struct TestCase {
+            input: Vec<i32>,
+            expected: Vec<i32>,
+        }
+
+        let test_cases = vec![TestCase {
+            input: vec![1, 4, 2, 3, 5, 111, 234, 21, 13],
+            expected: vec![234, 111, 21, 13, 5, 4, 3, 2, 1],
+        }];
+
+        for case in test_cases {
+            let mut actual = case.input.clone();
+            super::sort_reverse(&mut actual).unwrap();
+            assert_eq!(actual, case.expected);
+        }
+    }
+
+    #[test]
+    fn sort_by_key_stable() {
+        let input = vec![(3, 'a'), (1, 'b'), (3, 'c'), (-2, 'd'), (1, 'e'), (3, 'f')];
+
+        let mut actual = input.clone();
+        super::sort_by_key(&mut actual, |e| e.0).unwrap();
+        assert_eq!(
+            actual,
+            vec![(-2, 'd'), (1, 'b'), (1, 'e'), (3, 'a'), (3, 'c'), (3, 'f')]
+        );
+
+        let mut actual = input;
+        super::sort_by_key_reverse(&mut actual, |e| e.0).unwrap();
+        assert_eq!(
+            actual,
+            vec![(3, 'a'), (3, 'c'), (3, 'f'), (1, 'b'), (1, 'e'), (-2, 'd')]
+        );
+    }
+
+    #[test]
+    fn sort_errors() {
+        let mut actual = vec![u64::MAX, 1];
+        assert!(super::sort(&mut actual).is_err());
+        assert_eq!(actual, vec![u64::MAX, 1]);
+
+        let mut actual = vec![isize::MIN, isize::MAX];
+        assert!(super::sort(&mut actual).is_err());
+    }
+}
@@ -52,61 +52,75 @@ where
     T: std::cmp::Ord + std::clone::Clone,
     F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
 {
-    _quick_sort_impl(array, compare)
+    _quick_sort_impl(array, compare, utils::swap)
 }
 
-fn _quick_sort_impl<T, F>(array: &mut [T], compare: F)
+pub(crate) fn _quick_sort_impl<T, F, S>(array: &mut [T], compare: F, mut swap: S)
 where
-    T: std::cmp::Ord + std::clone::Clone,
     F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
+    S: FnMut(&mut [T], usize, usize),
 {
     if array.len() == 0 {
         return;
     }
-    _quick_sort_recursive(array, 0, array.len() - 1, compare)
+    _quick_sort_recursive(array, 0, array.len() - 1, compare, &mut swap)
 }
 
 // implementation
 
 // recurive
-fn _quick_sort_recursive<T, F>(array: &mut [T], left: usize, right: usize, compare: F)
-where
-    T: std::cmp::Ord + std::clone::Clone,
+fn _quick_sort_recursive<T, F, S>(
+    array: &mut [T],
+    left: usize,
+    right: usize,
+    compare: F,
+    swap: &mut S,
+) where
     F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
+    S: FnMut(&mut [T], usize, usize),
 {
     if left >= right {
         return;
     }
 
-    let pivot = _quick_partition(array, left, right, compare.clone());
+    let pivot = _quick_partition(array, left, right, compare.clone(), swap);
 
-    _quick_sort_recursive(array, left, pivot, compare.clone());
-    _quick_sort_recursive(array, pivot + 1, right, compare);
+    // The pivot is in place, so neither side includes it.
+    if pivot > left {
+        _quick_sort_recursive(array, left, pivot - 1, compare.clone(), swap);
+    }
+    _quick_sort_recursive(array, pivot + 1, right, compare, swap);
 }
 
-fn _quick_partition<T, F>(array: &mut [T], left: usize, right: usize, compare: F) -> usize
+// The pivot stays at array[left] until it's swapped into place:
+// l moves past left before anything is swapped.
+fn _quick_partition<T, F, S>(
+    array: &mut [T],
+    left: usize,
+    right: usize,
+    compare: F,
+    swap: &mut S,
+) -> usize
 where
-    T: std::cmp::Ord + std::clone::Clone,
     F: Fn(&T, &T) -> std::cmp::Ordering,
+    S: FnMut(&mut [T], usize, usize),
 {
-    let pivot = array[left].clone();
     let mut l = left;
     let mut r = right;
 
     while l < r {
-        while compare(&pivot, &array[r]) == std::cmp::Ordering::Less {
+        while compare(&array[left], &array[r]) == std::cmp::Ordering::Less {
             r -= 1;
         }
 
-        while l < r && compare(&pivot, &array[l]) != std::cmp::Ordering::Less {
+        while l < r && compare(&array[left], &array[l]) != std::cmp::Ordering::Less {
             l += 1;
         }
 
-        utils::swap(array, l, r);
+        swap(array, l, r);
     }
 
-    array[left] = array[l].clone();
-    array[l] = pivot;
+    swap(array, left, l);
 
     return l;
 }
@@ -149,4 +163,12 @@ mod tests {
             assert_eq!(actual, case.expected);
         }
     }
+    #[test]
+    fn sort_equal() {
+        let mut actual = vec![2; 1000];
+        actual.extend(vec![1; 1000]);
+        super::sort(&mut actual);
+        assert_eq!(actual[..1000], vec![1; 1000][..]);
+        assert_eq!(actual[1000..], vec![2; 1000][..]);
+    }
 }
@@ -1,5 +1,9 @@
 //! radix sort algorithm.
 //!
+//! [`sort`] and [`sort_reverse`] sort integers digit by digit in the given radix.
+//! [`sort_keys`], [`sort_by_key`] and their reverses sort [`RadixKey`]s byte by byte.
+//!
+//! stable sort: every entry point is a LSD radix sort  
 //! **O(wN)**: w=length of key
 
 use std::convert::{TryFrom, TryInto};
@@ -44,6 +48,286 @@ where
     _radix_sort_scan_impl(array, radix, false)
 }
 
+/// Sort in ascending order using a LSD radix sort algorithm over the bytes of the keys.
+///
+/// Every integer type, `f32`, `f64`, `char`, `bool` and tuples of them are [`RadixKey`]s.
+/// Floats are sorted in total order, so `-0.0` comes before `0.0` and NaNs go to the ends.
+///
+/// ```rust
+/// use buldak::radix;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, -33, 234, 21, 13];
+/// radix::sort_keys(&mut nums);
+/// assert_eq!(nums, [-33, 1, 2, 3, 4, 5, 13, 21, 111, 234]);
+///
+/// let mut floats = [1.5, -0.25, 3.0, -8.0];
+/// radix::sort_keys(&mut floats);
+/// assert_eq!(floats, [-8.0, -0.25, 1.5, 3.0]);
+/// ```
+pub fn sort_keys<K>(array: &mut [K])
+where
+    K: RadixKey + std::clone::Clone,
+{
+    _radix_sort_by_key_impl(array, |e| e.clone(), true)
+}
+
+/// Sort in descending order using a LSD radix sort algorithm over the bytes of the keys.
+///
+/// ```rust
+/// use buldak::radix;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, -33, 234, 21, 13];
+/// radix::sort_keys_reverse(&mut nums);
+/// assert_eq!(nums, [234, 111, 21, 13, 5, 4, 3, 2, 1, -33]);
+/// ```
+pub fn sort_keys_reverse<K>(array: &mut [K])
+where
+    K: RadixKey + std::clone::Clone,
+{
+    _radix_sort_by_key_impl(array, |e| e.clone(), false)
+}
+
+/// It takes a function that extracts a [`RadixKey`] from each element,
+/// and sorts it in ascending order of the keys using a LSD radix sort algorithm.
+///
+/// ```rust
+/// use buldak::radix;
+///
+/// let mut people = [("kim", 31u8), ("lee", 24), ("park", 31), ("choi", 19)];
+/// radix::sort_by_key(&mut people, |e| e.1);
+/// assert_eq!(people, [("choi", 19), ("lee", 24), ("kim", 31), ("park", 31)]);
+/// ```
+pub fn sort_by_key<T, K, F>(array: &mut [T], key: F)
+where
+    T: std::clone::Clone,
+    K: RadixKey,
+    F: Fn(&T) -> K,
+{
+    _radix_sort_by_key_impl(array, key, true)
+}
+
+/// It takes a function that extracts a [`RadixKey`] from each element,
+/// and sorts it in descending order of the keys using a LSD radix sort algorithm.
+///
+/// ```rust
+/// use buldak::radix;
+///
+/// let mut people = [("kim", 31u8), ("lee", 24), ("park", 31), ("choi", 19)];
+/// radix::sort_by_key_reverse(&mut people, |e| e.1);
+/// assert_eq!(people, [("kim", 31), ("park", 31), ("lee", 24), ("choi", 19)]);
+/// ```
+pub fn sort_by_key_reverse<T, K, F>(array: &mut [T], key: F)
+where
+    T: std::clone::Clone,
+    K: RadixKey,
+    F: Fn(&T) -> K,
+{
+    _radix_sort_by_key_impl(array, key, false)
+}
+
+/// A key that can be sorted one byte at a time.
+///
+/// The bytes must order like the key itself when compared from the most significant one.
+pub trait RadixKey {
+    /// The number of bytes in the key.
+    const BYTES: usize;
+
+    /// The byte at `index`, counting from the least significant byte.
+    fn byte(&self, index: usize) -> u8;
+}
+
+macro_rules! impl_radix_key_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl RadixKey for $t {
+                const BYTES: usize = std::mem::size_of::<$t>();
+
+                fn byte(&self, index: usize) -> u8 {
+                    (*self >> (index * 8)) as u8
+                }
+            }
+        )*
+    };
+}
+
+// flipping the sign bit orders two's complement values like unsigned ones
+macro_rules! impl_radix_key_signed {
+    ($($t:ty => $u:ty),*) => {
+        $(
+            impl RadixKey for $t {
+                const BYTES: usize = std::mem::size_of::<$t>();
+
+                fn byte(&self, index: usize) -> u8 {
+                    ((*self as $u) ^ (1 << (<$u>::BITS - 1))).byte(index)
+                }
+            }
+        )*
+    };
+}
+
+// negative floats have every bit flipped, positive ones only the sign bit
+macro_rules! impl_radix_key_float {
+    ($($t:ty => $u:ty),*) => {
+        $(
+            impl RadixKey for $t {
+                const BYTES: usize = std::mem::size_of::<$t>();
+
+                fn byte(&self, index: usize) -> u8 {
+                    let bits = self.to_bits();
+                    let sign: $u = 1 << (<$u>::BITS - 1);
+                    let ordered = if bits & sign != 0 { !bits } else { bits | sign };
+                    ordered.byte(index)
+                }
+            }
+        )*
+    };
+}
+
+impl_radix_key_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_radix_key_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+impl_radix_key_float!(f32 => u32, f64 => u64);
+
+impl RadixKey for char {
+    const BYTES: usize = 4;
+
+    fn byte(&self, index: usize) -> u8 {
+        (*self as u32).byte(index)
+    }
+}
+
+impl RadixKey for bool {
+    const BYTES: usize = 1;
+
+    fn byte(&self, _index: usize) -> u8 {
+        *self as u8
+    }
+}
+
+// the last element of a tuple holds the least significant bytes
+impl<A, B> RadixKey for (A, B)
+where
+    A: RadixKey,
+    B: RadixKey,
+{
+    const BYTES: usize = A::BYTES + B::BYTES;
+
+    fn byte(&self, index: usize) -> u8 {
+        if index < B::BYTES {
+            self.1.byte(index)
+        } else {
+            self.0.byte(index - B::BYTES)
+        }
+    }
+}
+
+impl<A, B, C> RadixKey for (A, B, C)
+where
+    A: RadixKey,
+    B: RadixKey,
+    C: RadixKey,
+{
+    const BYTES: usize = A::BYTES + B::BYTES + C::BYTES;
+
+    fn byte(&self, index: usize) -> u8 {
+        if index < C::BYTES {
+            self.2.byte(index)
+        } else if index < C::BYTES + B::BYTES {
+            self.1.byte(index - C::BYTES)
+        } else {
+            self.0.byte(index - C::BYTES - B::BYTES)
+        }
+    }
+}
+
+impl<A, B, C, D> RadixKey for (A, B, C, D)
+where
+    A: RadixKey,
+    B: RadixKey,
+    C: RadixKey,
+    D: RadixKey,
+{
+    const BYTES: usize = A::BYTES + B::BYTES + C::BYTES + D::BYTES;
+
+    fn byte(&self, index: usize) -> u8 {
+        if index < D::BYTES {
+            self.3.byte(index)
+        } else if index < D::BYTES + C::BYTES {
+            self.2.byte(index - D::BYTES)
+        } else if index < D::BYTES + C::BYTES + B::BYTES {
+            self.1.byte(index - D::BYTES - C::BYTES)
+        } else {
+            self.0.byte(index - D::BYTES - C::BYTES - B::BYTES)
+        }
+    }
+}
+
+// One counting pass per byte, moving elements between the array and a single scratch buffer.
+// Bytes that are the same for every element are skipped.
+fn _radix_sort_by_key_impl<T, K, F>(array: &mut [T], key: F, asc: bool)
+where
+    T: std::clone::Clone,
+    K: RadixKey,
+    F: Fn(&T) -> K,
+{
+    let len = array.len();
+    if len <= 1 {
+        return;
+    }
+
+    let mut counts = vec![[0usize; 256]; K::BYTES];
+    for e in array.iter() {
+        let k = key(e);
+        for (index, count) in counts.iter_mut().enumerate() {
+            count[k.byte(index) as usize] += 1;
+        }
+    }
+
+    let mut buffer: Vec<T> = vec![];
+    let mut in_buffer = false;
+
+    for (index, count) in counts.iter().enumerate() {
+        if count.contains(&len) {
+            continue;
+        }
+
+        let mut offsets = [0usize; 256];
+        let mut total = 0;
+        for digit in 0..256 {
+            let digit = if asc { digit } else { 255 - digit };
+            offsets[digit] = total;
+            total += count[digit];
+        }
+
+        if buffer.is_empty() {
+            buffer = array.to_vec();
+        }
+
+        if in_buffer {
+            _scatter(&buffer, array, index, &mut offsets, &key);
+        } else {
+            _scatter(array, &mut buffer, index, &mut offsets, &key);
+        }
+        in_buffer = !in_buffer;
+    }
+
+    if in_buffer {
+        array.clone_from_slice(&buffer);
+    }
+}
+
+fn _scatter<T, K, F>(from: &[T], to: &mut [T], index: usize, offsets: &mut [usize; 256], key: &F)
+where
+    T: std::clone::Clone,
+    K: RadixKey,
+    F: Fn(&T) -> K,
+{
+    for e in from.iter() {
+        let digit = key(e).byte(index) as usize;
+        to[offsets[digit]] = e.clone();
+        offsets[digit] += 1;
+    }
+}
+
 fn _radix_sort_impl<T>(
     array: &mut [T],
     digits_max: usize,
@@ -80,9 +364,10 @@ where
 
         let mut pos = 0;
 
+        // negative values are ordered by descending absolute value, so their digits go backwards
         if asc {
-            for i in 0_isize..(neg_counter.len() as isize) {
-                while let Some(value) = neg_counter[i as usize].pop_back() {
+            for i in (0_isize..(neg_counter.len() as isize)).rev() {
+                while let Some(value) = neg_counter[i as usize].pop_front() {
                     array[pos] = value;
                     pos += 1;
                 }
@@ -100,8 +385,8 @@ where
                     pos += 1;
                 }
             }
-            for i in (0_isize..(neg_counter.len() as isize)).rev() {
-                while let Some(value) = neg_counter[i as usize].pop_back() {
+            for i in 0_isize..(neg_counter.len() as isize) {
+                while let Some(value) = neg_counter[i as usize].pop_front() {
                     array[pos] = value;
                     pos += 1;
                 }
@@ -192,10 +477,16 @@ mod tests {
             expected: Vec<i32>,
         }
 
-        let test_cases = vec![TestCase {
-            input: vec![1, 4, 2, 3, 5, 111, 234, 21, 13],
-            expected: vec![1, 2, 3, 4, 5, 13, 21, 111, 234],
-        }];
+        let test_cases = vec![
+            TestCase {
+                input: vec![1, 4, 2, 3, 5, 111, 234, 21, 13],
+                expected: vec![1, 2, 3, 4, 5, 13, 21, 111, 234],
+            },
+            TestCase {
+                input: vec![-33, -1000, 5, 12, -2, 0, -4999],
+                expected: vec![-4999, -1000, -33, -2, 0, 5, 12],
+            },
+        ];
 
         for case in test_cases {
             let mut actual = case.input.clone();
@@ -222,4 +513,25 @@ mod tests {
             assert_eq!(actual, case.expected);
         }
     }
+
+    #[test]
+    fn sort_keys() {
+        let mut ints: Vec<i64> = vec![5, -3, i64::MIN, 0, i64::MAX, -1, 300];
+        super::sort_keys(&mut ints);
+        assert_eq!(ints, vec![i64::MIN, -3, -1, 0, 5, 300, i64::MAX]);
+
+        let mut floats = vec![2.5f64, -0.0, 0.0, -7.25, f64::INFINITY, -1e300, 1e-300];
+        super::sort_keys(&mut floats);
+        assert_eq!(
+            floats.iter().map(|e| e.to_bits()).collect::<Vec<_>>(),
+            [-1e300, -7.25, -0.0, 0.0, 1e-300, 2.5, f64::INFINITY]
+                .iter()
+                .map(|e: &f64| e.to_bits())
+                .collect::<Vec<_>>()
+        );
+
+        let mut pairs = vec![(2u8, 'b'), (1, 'z'), (2, 'a'), (1, 'a')];
+        super::sort_keys_reverse(&mut pairs);
+        assert_eq!(pairs, vec![(2, 'b'), (2, 'a'), (1, 'z'), (1, 'a')]);
+    }
 }
@@ -0,0 +1,421 @@
+//! selection algorithms.
+//!
+//! Like C++'s `nth_element`, they put the n-th element where it would be if sorted,
+//! with the elements before it not greater and the elements after it not less,
+//! without sorting the rest.
+//!
+//! unstable  
+//! **O(N)**: on average, and in the worst case for the median of medians
+
+mod utils;
+
+/// The ranges this short are sorted by an insertion sort instead.
+const INSERTION_THRESHOLD: usize = 16;
+
+/// The ranges longer than this are sampled first by a Floyd-Rivest selection.
+const SAMPLE_THRESHOLD: isize = 600;
+
+/// Puts the n-th smallest element at index n using an introselect algorithm.
+///
+/// It panics if n is out of bounds.
+///
+/// ```rust
+/// use buldak::select;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// select::nth_element(&mut nums, 6);
+/// assert_eq!(nums[6], 21);
+/// assert!(nums[..6].iter().all(|&e| e <= 21));
+/// assert!(nums[7..].iter().all(|&e| e >= 21));
+/// ```
+pub fn nth_element<T>(array: &mut [T], n: usize)
+where
+    T: std::cmp::Ord + std::clone::Clone,
+{
+    nth_element_by(array, n, |l, r| l.cmp(r))
+}
+
+/// It takes a comparator function to determine the order,
+/// and puts the n-th element in that order at index n using an introselect algorithm.
+///
+/// It partitions like the intro sort, and switches to the median of medians
+/// when the partitions get too unbalanced.
+///
+/// ```rust
+/// use buldak::select;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// select::nth_element_by(&mut nums, 0, |l, r| l.cmp(r).reverse());
+/// assert_eq!(nums[0], 234);
+/// ```
+pub fn nth_element_by<T, F>(array: &mut [T], n: usize, compare: F)
+where
+    T: std::clone::Clone,
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    _check_bounds(array.len(), n);
+    _intro_select_impl(array, n, &compare)
+}
+
+/// Puts the n-th smallest element at index n using a median of medians algorithm,
+/// which is **O(N)** in the worst case.
+///
+/// ```rust
+/// use buldak::select;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// select::median_of_medians(&mut nums, 2);
+/// assert_eq!(nums[2], 3);
+/// ```
+pub fn median_of_medians<T>(array: &mut [T], n: usize)
+where
+    T: std::cmp::Ord + std::clone::Clone,
+{
+    median_of_medians_by(array, n, |l, r| l.cmp(r))
+}
+
+/// It takes a comparator function to determine the order,
+/// and puts the n-th element in that order at index n using a median of medians algorithm.
+///
+/// ```rust
+/// use buldak::select;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// select::median_of_medians_by(&mut nums, 2, |l, r| l.cmp(r).reverse());
+/// assert_eq!(nums[2], 21);
+/// ```
+pub fn median_of_medians_by<T, F>(array: &mut [T], n: usize, compare: F)
+where
+    T: std::clone::Clone,
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    _check_bounds(array.len(), n);
+    _median_of_medians_select(array, 0, array.len() - 1, n, &compare)
+}
+
+/// Puts the n-th smallest element at index n using a Floyd-Rivest algorithm,
+/// which narrows down the range from a sample, and takes the fewest comparisons on average.
+///
+/// ```rust
+/// use buldak::select;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// select::floyd_rivest(&mut nums, 4);
+/// assert_eq!(nums[4], 5);
+/// ```
+pub fn floyd_rivest<T>(array: &mut [T], n: usize)
+where
+    T: std::cmp::Ord + std::clone::Clone,
+{
+    floyd_rivest_by(array, n, |l, r| l.cmp(r))
+}
+
+/// It takes a comparator function to determine the order,
+/// and puts the n-th element in that order at index n using a Floyd-Rivest algorithm.
+///
+/// ```rust
+/// use buldak::select;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// select::floyd_rivest_by(&mut nums, 4, |l, r| l.cmp(r).reverse());
+/// assert_eq!(nums[4], 5);
+/// ```
+pub fn floyd_rivest_by<T, F>(array: &mut [T], n: usize, compare: F)
+where
+    T: std::clone::Clone,
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    _check_bounds(array.len(), n);
+    _floyd_rivest_select(array, 0, array.len() as isize - 1, n as isize, &compare)
+}
+
+/// Returns the median, partitioning the array around it with an introselect algorithm.
+///
+/// For an even length, it's the lower of the two middle elements.
+/// It returns `None` for an empty array.
+///
+/// ```rust
+/// use buldak::select;
+///
+/// let mut nums = [1, 4, 2, 3, 5, 111, 234, 21, 13];
+/// assert_eq!(select::median(&mut nums), Some(&5));
+///
+/// let mut nums = [4, 1, 3, 2];
+/// assert_eq!(select::median(&mut nums), Some(&2));
+///
+/// assert_eq!(select::median::<i32>(&mut []), None);
+/// ```
+pub fn median<T>(array: &mut [T]) -> Option<&T>
+where
+    T: std::cmp::Ord + std::clone::Clone,
+{
+    median_by(array, |l, r| l.cmp(r))
+}
+
+/// It takes a comparator function to determine the order,
+/// and returns the median in that order, partitioning the array around it.
+///
+/// ```rust
+/// use buldak::select;
+///
+/// let mut words = ["ccc", "a", "dddd", "bb"];
+/// assert_eq!(select::median_by(&mut words, |l, r| l.len().cmp(&r.len())), Some(&"bb"));
+/// ```
+pub fn median_by<T, F>(array: &mut [T], compare: F) -> Option<&T>
+where
+    T: std::clone::Clone,
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    if array.is_empty() {
+        return None;
+    }
+
+    let middle = (array.len() - 1) / 2;
+    _intro_select_impl(array, middle, &compare);
+    Some(&array[middle])
+}
+
+fn _check_bounds(len: usize, n: usize) {
+    if n >= len {
+        panic!("The index is {} but the length is {}.", n, len);
+    }
+}
+
+fn _intro_select_impl<T, F>(array: &mut [T], n: usize, compare: &F)
+where
+    T: std::clone::Clone,
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    let mut left = 0;
+    let mut right = array.len() - 1;
+    let mut max_depth = (array.len() as f64).log2().floor() as usize * 2;
+
+    loop {
+        if right - left <= INSERTION_THRESHOLD {
+            crate::intro::_insertion_sort(array, left, right, compare);
+            return;
+        }
+
+        if max_depth == 0 {
+            _median_of_medians_select(array, left, right, n, compare);
+            return;
+        }
+        max_depth -= 1;
+
+        let middle = left + (right - left) / 2;
+        let pivot = crate::intro::_find_pivot(array, left, middle, right, compare);
+        utils::swap(array, pivot, right);
+
+        let pivot = crate::intro::_intro_partition(array, left, right, compare);
+        match n.cmp(&pivot) {
+            std::cmp::Ordering::Equal => return,
+            std::cmp::Ordering::Less => right = pivot - 1,
+            std::cmp::Ordering::Greater => left = pivot + 1,
+        }
+    }
+}
+
+// Selects within array[left..=right]. The pivot is the median of the medians of groups of 5,
+// and the partition is three-way so that duplicates don't make it quadratic.
+fn _median_of_medians_select<T, F>(
+    array: &mut [T],
+    mut left: usize,
+    mut right: usize,
+    n: usize,
+    compare: &F,
+) where
+    T: std::clone::Clone,
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    loop {
+        if right - left < 5 {
+            crate::intro::_insertion_sort(array, left, right, compare);
+            return;
+        }
+
+        let pivot = _pivot_of_medians(array, left, right, compare);
+        let (less, greater) = _three_way_partition(array, left, right, pivot, compare);
+
+        if n < less {
+            right = less - 1;
+        } else if n > greater {
+            left = greater + 1;
+        } else {
+            return;
+        }
+    }
+}
+
+// Moves the median of each group of 5 to the front, and returns the index of their median.
+fn _pivot_of_medians<T, F>(array: &mut [T], left: usize, right: usize, compare: &F) -> usize
+where
+    T: std::clone::Clone,
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    let mut medians = left;
+    for start in (left..=right).step_by(5) {
+        let end = std::cmp::min(start + 4, right);
+        crate::intro::_insertion_sort(array, start, end, compare);
+        utils::swap(array, start + (end - start) / 2, medians);
+        medians += 1;
+    }
+
+    let middle = left + (medians - 1 - left) / 2;
+    _median_of_medians_select(array, left, medians - 1, middle, compare);
+    middle
+}
+
+// Returns the first and the last index of the elements equal to the pivot after partitioning.
+fn _three_way_partition<T, F>(
+    array: &mut [T],
+    left: usize,
+    right: usize,
+    pivot: usize,
+    compare: &F,
+) -> (usize, usize)
+where
+    T: std::clone::Clone,
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    let pivot = array[pivot].clone();
+
+    // array[left..less] < pivot, array[less..i] == pivot, array[greater + 1..=right] > pivot
+    let mut less = left;
+    let mut i = left;
+    let mut greater = right;
+
+    while i <= greater {
+        match compare(&array[i], &pivot) {
+            std::cmp::Ordering::Less => {
+                utils::swap(array, less, i);
+                less += 1;
+                i += 1;
+            }
+            std::cmp::Ordering::Equal => i += 1,
+            std::cmp::Ordering::Greater => {
+                utils::swap(array, i, greater);
+                if greater == 0 {
+                    break;
+                }
+                greater -= 1;
+            }
+        }
+    }
+
+    (less, greater)
+}
+
+// Floyd and Rivest's SELECT. A range that is long enough is narrowed down to where
+// the n-th element should be by selecting recursively within a sample first.
+fn _floyd_rivest_select<T, F>(
+    array: &mut [T],
+    mut left: isize,
+    mut right: isize,
+    n: isize,
+    compare: &F,
+) where
+    T: std::clone::Clone,
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    use std::cmp::Ordering::{Equal, Greater, Less};
+
+    while right > left {
+        if right - left > SAMPLE_THRESHOLD {
+            let len = (right - left + 1) as f64;
+            let i = (n - left + 1) as f64;
+            let z = len.ln();
+            let s = 0.5 * (2.0 * z / 3.0).exp();
+            let sd = 0.5 * (z * s * (len - s) / len).sqrt() * (i - len / 2.0).signum();
+
+            let sample_left = std::cmp::max(left, (n as f64 - i * s / len + sd) as isize);
+            let sample_right = std::cmp::min(right, (n as f64 + (len - i) * s / len + sd) as isize);
+            _floyd_rivest_select(array, sample_left, sample_right, n, compare);
+        }
+
+        let pivot = array[n as usize].clone();
+        let mut i = left;
+        let mut j = right;
+
+        utils::swap(array, left as usize, n as usize);
+        if compare(&array[right as usize], &pivot) == Greater {
+            utils::swap(array, right as usize, left as usize);
+        }
+
+        while i < j {
+            utils::swap(array, i as usize, j as usize);
+            i += 1;
+            j -= 1;
+            while compare(&array[i as usize], &pivot) == Less {
+                i += 1;
+            }
+            while compare(&array[j as usize], &pivot) == Greater {
+                j -= 1;
+            }
+        }
+
+        if compare(&array[left as usize], &pivot) == Equal {
+            utils::swap(array, left as usize, j as usize);
+        } else {
+            j += 1;
+            utils::swap(array, j as usize, right as usize);
+        }
+
+        if j <= n {
+            left = j + 1;
+        }
+        if n <= j {
+            right = j - 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    fn check(actual: &[i64], n: usize, expected: &[i64]) {
+        assert_eq!(actual[n], expected[n]);
+        assert!(actual[..n].iter().all(|&e| e <= expected[n]));
+        assert!(actual[n + 1..].iter().all(|&e| e >= expected[n]));
+    }
+
+    #[test]
+    fn nth_element() {
+        let inputs: Vec<Vec<i64>> = vec![
+            vec![1, 4, 2, 3, 5, 111, 234, 21, 13],
+            (0..2_000).map(|i| (i * 7919) % 2_003).collect(),
+            (0..2_000).map(|i| (i * 37) % 10).collect(),
+            vec![5; 100],
+            (0..1_000).rev().collect(),
+        ];
+
+        for input in inputs {
+            let mut expected = input.clone();
+            expected.sort();
+
+            for n in [0, 1, input.len() / 3, input.len() / 2, input.len() - 1] {
+                let mut actual = input.clone();
+                super::nth_element(&mut actual, n);
+                check(&actual, n, &expected);
+
+                let mut actual = input.clone();
+                super::median_of_medians(&mut actual, n);
+                check(&actual, n, &expected);
+
+                let mut actual = input.clone();
+                super::floyd_rivest(&mut actual, n);
+                check(&actual, n, &expected);
+            }
+
+            let mut actual = input.clone();
+            assert_eq!(
+                super::median(&mut actual),
+                Some(&expected[(expected.len() - 1) / 2])
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn nth_element_out_of_bounds() {
+        super::nth_element(&mut [1, 2, 3], 3);
+    }
+}
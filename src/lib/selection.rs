@@ -52,13 +52,13 @@ where
     T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering,
 {
-    _selection_sort_impl(array, compare)
+    _selection_sort_impl(array, compare, utils::swap)
 }
 
-fn _selection_sort_impl<T, F>(array: &mut [T], compare: F)
+pub(crate) fn _selection_sort_impl<T, F, S>(array: &mut [T], compare: F, mut swap: S)
 where
-    T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering,
+    S: FnMut(&mut [T], usize, usize),
 {
     let mut last = array.len() - 1;
 
@@ -75,7 +75,7 @@ where
             i += 1;
         }
 
-        utils::swap(array, last, max_i);
+        swap(array, last, max_i);
 
         last -= 1;
     }
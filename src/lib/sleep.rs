@@ -3,6 +3,98 @@
 //! unstable sort  
 //! **O(N)**
 
+/// The time an element sleeps for each unit of its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    Seconds,
+    Milliseconds,
+    Microseconds,
+}
+
+/// How the sleeping elements are woken up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheduler {
+    /// One thread sleeps for every element.
+    Threads,
+    /// A single thread ticks a timer wheel, waking the elements due at each tick in order.
+    TimerWheel,
+    /// No real time passes. A simulated clock wakes the elements in the order
+    /// the timer wheel would, so the result is always the same.
+    Simulated,
+}
+
+/// The settings of [`sort_with_config`].
+///
+/// ```rust
+/// use buldak::sleep;
+///
+/// let config = sleep::Config::new()
+///     .unit(sleep::Unit::Microseconds)
+///     .offset(100)
+///     .scheduler(sleep::Scheduler::TimerWheel);
+/// assert_eq!(config.get_offset(), 100);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    unit: Unit,
+    offset: isize,
+    scheduler: Scheduler,
+}
+
+impl Config {
+    /// Sleeps a second per unit, with no offset, on a thread per element.
+    pub fn new() -> Self {
+        Config {
+            unit: Unit::Seconds,
+            offset: 0,
+            scheduler: Scheduler::Threads,
+        }
+    }
+
+    /// Sets the time slept for each unit of a value.
+    pub fn unit(mut self, unit: Unit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Sets the value added to every element before sleeping,
+    /// so that negative values can be sorted.
+    pub fn offset(mut self, offset: isize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Sets how the sleeping elements are woken up.
+    pub fn scheduler(mut self, scheduler: Scheduler) -> Self {
+        self.scheduler = scheduler;
+        self
+    }
+
+    /// The time slept for each unit of a value.
+    pub fn get_unit(&self) -> Unit {
+        self.unit
+    }
+
+    /// The value added to every element before sleeping.
+    pub fn get_offset(&self) -> isize {
+        self.offset
+    }
+
+    /// How the sleeping elements are woken up.
+    pub fn get_scheduler(&self) -> Scheduler {
+        self.scheduler
+    }
+}
+
+impl std::default::Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The number of slots of the timer wheel.
+const WHEEL_SIZE: usize = 256;
+
 /// Sort in ascending order using a sleep sort algorithm.
 ///
 /// ```rust
@@ -14,16 +106,9 @@
 /// ```
 pub fn sort<T>(array: &mut [T]) -> Result<(), String>
 where
-    T: std::convert::TryInto<usize>
-        + std::convert::TryFrom<usize>
-        + std::clone::Clone
-        + std::marker::Sync
-        + std::marker::Send
-        + 'static,
-    <T as std::convert::TryInto<usize>>::Error: std::fmt::Debug,
-    <T as std::convert::TryFrom<usize>>::Error: std::fmt::Debug,
+    T: std::convert::TryInto<isize> + std::clone::Clone + std::marker::Send,
 {
-    _sleep_sort_impl(array, true)
+    _sleep_sort_impl(array, &Config::new(), true)
 }
 
 /// Sort in descending order using a sleep algorithm.
@@ -37,66 +122,165 @@ where
 /// ```
 pub fn sort_reverse<T>(array: &mut [T]) -> Result<(), String>
 where
-    T: std::convert::TryInto<usize>
-        + std::convert::TryFrom<usize>
-        + std::clone::Clone
-        + std::marker::Sync
-        + std::marker::Send
-        + 'static,
-    <T as std::convert::TryInto<usize>>::Error: std::fmt::Debug,
-    <T as std::convert::TryFrom<usize>>::Error: std::fmt::Debug,
+    T: std::convert::TryInto<isize> + std::clone::Clone + std::marker::Send,
 {
-    _sleep_sort_impl(array, false)
+    _sleep_sort_impl(array, &Config::new(), false)
 }
 
-fn _sleep_sort_impl<T>(array: &mut [T], asc: bool) -> Result<(), String>
+/// Sort in ascending order using a sleep sort algorithm with the given [`Config`].
+///
+/// It fails if a value plus the offset is negative.
+///
+/// ```rust
+/// use buldak::sleep;
+///
+/// let config = sleep::Config::new()
+///     .offset(50)
+///     .scheduler(sleep::Scheduler::Simulated);
+///
+/// let mut nums = [6, -40, 2, 3, 1, 5];
+/// sleep::sort_with_config(&mut nums, &config).unwrap();
+/// assert_eq!(nums, [-40, 1, 2, 3, 5, 6]);
+///
+/// assert!(sleep::sort_with_config(&mut [-51], &config).is_err());
+/// ```
+pub fn sort_with_config<T>(array: &mut [T], config: &Config) -> Result<(), String>
 where
-    T: std::convert::TryInto<usize>
-        + std::convert::TryFrom<usize>
-        + std::clone::Clone
-        + std::marker::Sync
-        + std::marker::Send
-        + 'static,
-    <T as std::convert::TryInto<usize>>::Error: std::fmt::Debug,
-    <T as std::convert::TryFrom<usize>>::Error: std::fmt::Debug,
+    T: std::convert::TryInto<isize> + std::clone::Clone + std::marker::Send,
 {
-    use std::sync::{Arc, Mutex};
-    use std::{thread, time};
+    _sleep_sort_impl(array, config, true)
+}
 
-    let original = Arc::new(Mutex::new(array.to_owned()));
+/// Sort in descending order using a sleep sort algorithm with the given [`Config`].
+///
+/// ```rust
+/// use buldak::sleep;
+///
+/// let config = sleep::Config::new()
+///     .unit(sleep::Unit::Microseconds)
+///     .scheduler(sleep::Scheduler::TimerWheel);
+///
+/// let mut nums = [6, 4, 2, 3, 1, 5];
+/// sleep::sort_reverse_with_config(&mut nums, &config).unwrap();
+/// assert_eq!(nums, [6, 5, 4, 3, 2, 1]);
+/// ```
+pub fn sort_reverse_with_config<T>(array: &mut [T], config: &Config) -> Result<(), String>
+where
+    T: std::convert::TryInto<isize> + std::clone::Clone + std::marker::Send,
+{
+    _sleep_sort_impl(array, config, false)
+}
 
-    let shared = Arc::new(Mutex::new(vec![]));
-    let mut handlers = vec![];
+fn _sleep_sort_impl<T>(array: &mut [T], config: &Config, asc: bool) -> Result<(), String>
+where
+    T: std::convert::TryInto<isize> + std::clone::Clone + std::marker::Send,
+{
+    let unit = match config.unit {
+        Unit::Seconds => std::time::Duration::from_secs(1),
+        Unit::Milliseconds => std::time::Duration::from_millis(1),
+        Unit::Microseconds => std::time::Duration::from_micros(1),
+    };
 
-    for e in original.lock().unwrap().iter().cloned() {
-        let data = Arc::clone(&shared);
+    let mut ticks = Vec::with_capacity(array.len());
+    for e in array.iter() {
+        let e: isize = e.clone().try_into().map_err(|_| {
+            "Conversion to isize failed. All elements of the array must be convertible to isize."
+                .to_string()
+        })?;
+
+        let tick = (e as i128) + (config.offset as i128);
+        if tick < 0 {
+            return Err("A value plus the offset is negative, so it can't sleep.".to_string());
+        }
+        ticks.push(tick as u64);
+    }
 
-        let n: usize = e.to_owned().try_into().unwrap();
+    // the order in which the elements woke up, as indices into the array
+    let order = match config.scheduler {
+        Scheduler::Threads => _wake_on_threads(&ticks, unit),
+        Scheduler::TimerWheel => _wake_on_timer_wheel(&ticks, Some(unit)),
+        Scheduler::Simulated => _wake_on_timer_wheel(&ticks, None),
+    };
 
-        handlers.push(thread::spawn(move || {
-            thread::sleep(time::Duration::from_secs(n as u64));
-            data.lock().unwrap().push(e);
-        }));
+    let original = array.to_vec();
+    let len = array.len();
+    for (i, index) in order.into_iter().enumerate() {
+        let position = if asc { i } else { len - i - 1 };
+        array[position] = original[index].clone();
     }
 
-    for handler in handlers {
-        handler.join().unwrap();
+    Ok(())
+}
+
+fn _wake_on_threads(ticks: &[u64], unit: std::time::Duration) -> Vec<usize> {
+    use std::sync::Mutex;
+    use std::thread;
+
+    let shared = Mutex::new(vec![]);
+
+    thread::scope(|scope| {
+        for (index, &tick) in ticks.iter().enumerate() {
+            let shared = &shared;
+
+            scope.spawn(move || {
+                thread::sleep(_after(unit, tick));
+                shared.lock().unwrap().push(index);
+            });
+        }
+    });
+
+    shared.into_inner().unwrap()
+}
+
+fn _after(unit: std::time::Duration, ticks: u64) -> std::time::Duration {
+    std::time::Duration::from_nanos((unit.as_nanos() as u64).saturating_mul(ticks))
+}
+
+// Elements due at the same tick wake up in the order of the array.
+// Without a unit, the clock is simulated and skips the turns of the wheel where nothing is due.
+fn _wake_on_timer_wheel(ticks: &[u64], unit: Option<std::time::Duration>) -> Vec<usize> {
+    // each slot holds (how many more turns of the wheel to wait, index)
+    let mut wheel: Vec<Vec<(u64, usize)>> = vec![vec![]; WHEEL_SIZE];
+    for (index, &tick) in ticks.iter().enumerate() {
+        wheel[(tick % WHEEL_SIZE as u64) as usize].push((tick / WHEEL_SIZE as u64, index));
     }
 
-    let result = shared.lock().unwrap();
+    let start = std::time::Instant::now();
+    let mut order = Vec::with_capacity(ticks.len());
+    let mut now = 0u64;
 
-    if asc {
-        for i in 0..array.len() {
-            array[i] = result[i].clone();
+    while order.len() < ticks.len() {
+        if unit.is_none() && now.is_multiple_of(WHEEL_SIZE as u64) {
+            let idle = wheel.iter().flatten().map(|e| e.0).min().unwrap_or(0);
+            for (turns, _) in wheel.iter_mut().flatten() {
+                *turns -= idle;
+            }
+            now += idle * WHEEL_SIZE as u64;
         }
-    } else {
-        let len = array.len();
-        for i in 0..array.len() {
-            array[i] = result[len - i - 1].clone();
+
+        let slot = &mut wheel[(now % WHEEL_SIZE as u64) as usize];
+
+        if !slot.is_empty() {
+            if let Some(unit) = unit {
+                let deadline = start + _after(unit, now);
+                std::thread::sleep(deadline.saturating_duration_since(std::time::Instant::now()));
+            }
+
+            let mut waiting = vec![];
+            for (turns, index) in slot.drain(..) {
+                if turns == 0 {
+                    order.push(index);
+                } else {
+                    waiting.push((turns - 1, index));
+                }
+            }
+            *slot = waiting;
         }
+
+        now += 1;
     }
 
-    return Ok(());
+    order
 }
 
 mod tests {
@@ -137,4 +321,31 @@ mod tests {
             assert_eq!(actual, case.expected);
         }
     }
+
+    #[test]
+    fn sort_with_config() {
+        let input = vec![300, -7, 0, 1000, 256, 512, -7, 44];
+        let expected = vec![-7, -7, 0, 44, 256, 300, 512, 1000];
+
+        for scheduler in [super::Scheduler::Simulated, super::Scheduler::TimerWheel] {
+            let config = super::Config::new()
+                .unit(super::Unit::Microseconds)
+                .offset(7)
+                .scheduler(scheduler);
+
+            let mut actual = input.clone();
+            super::sort_with_config(&mut actual, &config).unwrap();
+            assert_eq!(actual, expected);
+        }
+
+        let config = super::Config::new().scheduler(super::Scheduler::Simulated);
+        let mut actual = input.clone();
+        assert!(super::sort_with_config(&mut actual, &config).is_err());
+        assert_eq!(actual, input);
+
+        let config = config.offset(isize::MAX);
+        let mut actual = vec![isize::MAX, 0, -isize::MAX, 1 << 40];
+        super::sort_with_config(&mut actual, &config).unwrap();
+        assert_eq!(actual, vec![-isize::MAX, 0, 1 << 40, isize::MAX]);
+    }
 }
@@ -54,13 +54,13 @@ where
     T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering,
 {
-    _smart_bubble_sort_impl(array, compare)
+    _smart_bubble_sort_impl(array, compare, utils::swap)
 }
 
-fn _smart_bubble_sort_impl<T, F>(array: &mut [T], compare: F)
+pub(crate) fn _smart_bubble_sort_impl<T, F, S>(array: &mut [T], compare: F, mut swap: S)
 where
-    T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering,
+    S: FnMut(&mut [T], usize, usize),
 {
     let mut last = array.len();
 
@@ -72,7 +72,7 @@ where
             match compare(&array[i], &array[i + 1]) {
                 std::cmp::Ordering::Less => (),
                 std::cmp::Ordering::Greater => {
-                    utils::swap(array, i, i + 1);
+                    swap(array, i, i + 1);
                     swap_flag = true;
                 }
                 std::cmp::Ordering::Equal => (),
@@ -0,0 +1,354 @@
+//! sorted vector collection.
+//!
+//! A vector that keeps its elements sorted,
+//! inserting with a binary insertion and extending with a tim sort and a merge.
+//!
+//! Equal elements keep the order they were added in.
+
+/// An order of the elements of a [`SortedVec`].
+///
+/// It's implemented for every comparator function, and for [`Natural`].
+pub trait Compare<T> {
+    fn compare(&self, l: &T, r: &T) -> std::cmp::Ordering;
+}
+
+/// The ascending order of [`std::cmp::Ord`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Natural;
+
+impl<T> Compare<T> for Natural
+where
+    T: std::cmp::Ord,
+{
+    fn compare(&self, l: &T, r: &T) -> std::cmp::Ordering {
+        l.cmp(r)
+    }
+}
+
+impl<T, F> Compare<T> for F
+where
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    fn compare(&self, l: &T, r: &T) -> std::cmp::Ordering {
+        self(l, r)
+    }
+}
+
+/// A vector that is always sorted in the order of `C`.
+///
+/// It dereferences to a slice, so it can be read like one.
+///
+/// ```rust
+/// use buldak::sorted_vec::SortedVec;
+///
+/// let mut nums = SortedVec::new();
+/// nums.insert(5);
+/// nums.insert(1);
+/// nums.extend(vec![4, 2, 3]);
+/// assert_eq!(nums.as_slice(), &[1, 2, 3, 4, 5]);
+/// assert_eq!(nums.range(2..4), &[2, 3]);
+///
+/// let mut words = SortedVec::with_compare(|l: &&str, r: &&str| l.len().cmp(&r.len()));
+/// words.extend(vec!["ccc", "a", "bb", "dd"]);
+/// assert_eq!(words.as_slice(), &["a", "bb", "dd", "ccc"]);
+/// ```
+#[derive(Clone)]
+pub struct SortedVec<T, C = Natural> {
+    data: Vec<T>,
+    compare: C,
+}
+
+impl<T> SortedVec<T, Natural>
+where
+    T: std::cmp::Ord,
+{
+    /// An empty vector in ascending order.
+    pub fn new() -> Self {
+        Self::with_compare(Natural)
+    }
+
+    /// Sorts the vector in ascending order with a tim sort, and keeps it sorted.
+    ///
+    /// ```rust
+    /// use buldak::sorted_vec::SortedVec;
+    ///
+    /// let nums = SortedVec::from_vec(vec![3, 1, 2]);
+    /// assert_eq!(nums.into_vec(), vec![1, 2, 3]);
+    /// ```
+    pub fn from_vec(data: Vec<T>) -> Self
+    where
+        T: std::clone::Clone,
+    {
+        Self::from_vec_with_compare(data, Natural)
+    }
+}
+
+impl<T> std::default::Default for SortedVec<T, Natural>
+where
+    T: std::cmp::Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, C> SortedVec<T, C>
+where
+    C: Compare<T>,
+{
+    /// An empty vector in the order of `compare`.
+    pub fn with_compare(compare: C) -> Self {
+        SortedVec {
+            data: vec![],
+            compare,
+        }
+    }
+
+    /// Sorts the vector in the order of `compare` with a tim sort, and keeps it sorted.
+    pub fn from_vec_with_compare(mut data: Vec<T>, compare: C) -> Self
+    where
+        T: std::clone::Clone,
+    {
+        crate::tim::sort_by(&mut data, |l, r| compare.compare(l, r));
+        SortedVec { data, compare }
+    }
+
+    /// Inserts the value after the elements not greater than it, and returns its position.
+    ///
+    /// The position is searched from the end, so values that arrive in order are inserted quickly.
+    ///
+    /// ```rust
+    /// use buldak::sorted_vec::SortedVec;
+    ///
+    /// let mut nums = SortedVec::from_vec(vec![1, 3, 5]);
+    /// assert_eq!(nums.insert(3), 2);
+    /// assert_eq!(nums.as_slice(), &[1, 3, 3, 5]);
+    /// ```
+    pub fn insert(&mut self, value: T) -> usize {
+        let compare = &self.compare;
+        let index = crate::binary_insertion::_gallop_upper_bound(&self.data, &value, &|l, r| {
+            compare.compare(l, r)
+        });
+
+        self.data.insert(index, value);
+        index
+    }
+
+    /// Removes and returns the element at the position.
+    ///
+    /// It panics if the index is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        self.data.remove(index)
+    }
+
+    /// Removes and returns the first element equal to the value, if there is one.
+    ///
+    /// ```rust
+    /// use buldak::sorted_vec::SortedVec;
+    ///
+    /// let mut nums = SortedVec::from_vec(vec![1, 2, 2, 3]);
+    /// assert_eq!(nums.remove_value(&2), Some(2));
+    /// assert_eq!(nums.remove_value(&4), None);
+    /// assert_eq!(nums.as_slice(), &[1, 2, 3]);
+    /// ```
+    pub fn remove_value(&mut self, value: &T) -> Option<T> {
+        let index = self.lower_bound(value);
+        if index < self.data.len()
+            && self.compare.compare(&self.data[index], value) == std::cmp::Ordering::Equal
+        {
+            Some(self.data.remove(index))
+        } else {
+            None
+        }
+    }
+
+    /// The position of the first element not less than the value.
+    pub fn lower_bound(&self, value: &T) -> usize {
+        self.data
+            .partition_point(|e| self.compare.compare(e, value) == std::cmp::Ordering::Less)
+    }
+
+    /// The position of the first element greater than the value.
+    pub fn upper_bound(&self, value: &T) -> usize {
+        self.data
+            .partition_point(|e| self.compare.compare(e, value) != std::cmp::Ordering::Greater)
+    }
+
+    /// The number of elements less than the value.
+    ///
+    /// ```rust
+    /// use buldak::sorted_vec::SortedVec;
+    ///
+    /// let nums = SortedVec::from_vec(vec![10, 20, 20, 30]);
+    /// assert_eq!(nums.rank(&20), 1);
+    /// assert_eq!(nums.rank(&25), 3);
+    /// ```
+    pub fn rank(&self, value: &T) -> usize {
+        self.lower_bound(value)
+    }
+
+    /// Whether an element is equal to the value, by a binary search.
+    pub fn contains(&self, value: &T) -> bool {
+        self.lower_bound(value) != self.upper_bound(value)
+    }
+
+    /// The elements within the range of values.
+    ///
+    /// ```rust
+    /// use buldak::sorted_vec::SortedVec;
+    ///
+    /// let nums = SortedVec::from_vec(vec![1, 2, 3, 4, 5]);
+    /// assert_eq!(nums.range(2..=4), &[2, 3, 4]);
+    /// assert_eq!(nums.range(..3), &[1, 2]);
+    /// ```
+    pub fn range<R>(&self, range: R) -> &[T]
+    where
+        R: std::ops::RangeBounds<T>,
+    {
+        use std::ops::Bound;
+
+        let start = match range.start_bound() {
+            Bound::Included(value) => self.lower_bound(value),
+            Bound::Excluded(value) => self.upper_bound(value),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(value) => self.upper_bound(value),
+            Bound::Excluded(value) => self.lower_bound(value),
+            Bound::Unbounded => self.data.len(),
+        };
+
+        &self.data[start..std::cmp::max(start, end)]
+    }
+
+    /// Removes all but the first of the equal elements.
+    ///
+    /// ```rust
+    /// use buldak::sorted_vec::SortedVec;
+    ///
+    /// let mut nums = SortedVec::from_vec(vec![1, 2, 2, 3, 3, 3]);
+    /// nums.dedup();
+    /// assert_eq!(nums.as_slice(), &[1, 2, 3]);
+    /// ```
+    pub fn dedup(&mut self) {
+        let compare = &self.compare;
+        self.data
+            .dedup_by(|r, l| compare.compare(l, r) == std::cmp::Ordering::Equal);
+    }
+
+    /// The sorted elements.
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    /// The sorted elements, as a vector.
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+}
+
+impl<T, C> std::ops::Deref for SortedVec<T, C> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.data
+    }
+}
+
+impl<T, C> std::fmt::Debug for SortedVec<T, C>
+where
+    T: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SortedVec").field(&self.data).finish()
+    }
+}
+
+// The new elements are sorted by themselves with a tim sort, then merged in.
+impl<T, C> std::iter::Extend<T> for SortedVec<T, C>
+where
+    T: std::clone::Clone,
+    C: Compare<T>,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: std::iter::IntoIterator<Item = T>,
+    {
+        let middle = self.data.len();
+        self.data.extend(iter);
+
+        let compare = &self.compare;
+        let compare = |l: &T, r: &T| compare.compare(l, r);
+        let right = self.data.len();
+
+        crate::tim::sort_by(&mut self.data[middle..], compare);
+        if middle > 0 && middle < right {
+            crate::tim::_merge(&mut self.data, 0, middle - 1, right - 1, compare);
+        }
+    }
+}
+
+impl<T, C> std::iter::IntoIterator for SortedVec<T, C> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+impl<'a, T, C> std::iter::IntoIterator for &'a SortedVec<T, C> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn insert_and_remove() {
+        let mut actual = super::SortedVec::with_compare(|l: &(i32, usize), r: &(i32, usize)| {
+            l.0.cmp(&r.0).reverse()
+        });
+
+        let input: Vec<(i32, usize)> = (0..100).map(|i| ((i * 37 % 11) as i32, i)).collect();
+        for &e in input.iter() {
+            actual.insert(e);
+        }
+
+        let mut expected = input;
+        expected.sort_by_key(|e| std::cmp::Reverse(e.0));
+        assert_eq!(actual.as_slice(), &expected[..]);
+
+        assert_eq!(actual.range((5, 0)..=(3, 0)).len(), 27);
+        assert_eq!(actual.remove_value(&(10, 0)), Some((10, 8)));
+        assert_eq!(actual.remove(0), (10, 19));
+
+        actual.dedup();
+        assert_eq!(actual.len(), 11);
+        assert_eq!(actual.rank(&(10, 0)), 0);
+        assert_eq!(actual.rank(&(0, 0)), 10);
+        assert!(!actual.contains(&(11, 0)));
+    }
+
+    #[test]
+    fn extend() {
+        let mut actual = super::SortedVec::from_vec(vec![9, 3, 7]);
+        actual.extend(vec![]);
+        actual.extend((0..100).rev().step_by(10));
+        actual.extend(vec![5, 5]);
+
+        let mut expected = vec![9, 3, 7, 5, 5];
+        expected.extend((0..100).rev().step_by(10));
+        expected.sort();
+        assert_eq!(actual.into_vec(), expected);
+
+        let mut actual: super::SortedVec<i32> = super::SortedVec::default();
+        actual.extend(vec![2, 1]);
+        assert_eq!(actual.range(3..), &[] as &[i32]);
+        assert_eq!(&actual[..], &[1, 2]);
+    }
+}
@@ -0,0 +1,517 @@
+//! spread sort algorithm.
+//!
+//! A hybrid of radix and comparison sorting in the style of Boost's spreadsort.
+//! Each range is split into bins by the most significant bits that still differ,
+//! and bins that get small, or whose keys are spread too widely for their size,
+//! are finished with an intro sort.
+//!
+//! unstable sort
+//! **O(N·min(log₂N, w))**: w=length of key
+
+mod utils;
+
+use crate::msd_radix::{_digit, DIGITS};
+use crate::radix::RadixKey;
+
+/// Ranges with at most this many elements are sorted with an intro sort.
+const MIN_SORT_SIZE: usize = 256;
+
+/// The most bits used to split a range at once.
+const MAX_SPLITS: u32 = 11;
+
+/// Bins get about 2^LOG_MEAN_BIN_SIZE elements on average.
+const LOG_MEAN_BIN_SIZE: u32 = 2;
+
+/// The fewest bits used to split a range, when the range is wide enough.
+const LOG_MIN_SPLIT_COUNT: u32 = 9;
+
+/// Sort in ascending order using a spread sort algorithm.
+///
+/// Every [`RadixKey`] can be sorted: integers, floats in total order, `char`, `bool` and tuples.
+///
+/// ```rust
+/// use buldak::spread;
+///
+/// let mut nums = [1, 4, 2, 3, 5, -44, 111, 234, 21, 13];
+/// spread::sort(&mut nums);
+/// assert_eq!(nums, [-44, 1, 2, 3, 4, 5, 13, 21, 111, 234]);
+///
+/// let mut floats = [2.5, -1.0, 0.0, -7.25];
+/// spread::sort(&mut floats);
+/// assert_eq!(floats, [-7.25, -1.0, 0.0, 2.5]);
+/// ```
+pub fn sort<T>(array: &mut [T])
+where
+    T: RadixKey + std::clone::Clone,
+{
+    _spread_sort_impl(array, &|e: &T| e.clone(), true)
+}
+
+/// Sort in descending order using a spread sort algorithm.
+///
+/// ```rust
+/// use buldak::spread;
+///
+/// let mut nums = [1, 4, 2, 3, 5, -44, 111, 234, 21, 13];
+/// spread::sort_reverse(&mut nums);
+/// assert_eq!(nums, [234, 111, 21, 13, 5, 4, 3, 2, 1, -44]);
+/// ```
+pub fn sort_reverse<T>(array: &mut [T])
+where
+    T: RadixKey + std::clone::Clone,
+{
+    _spread_sort_impl(array, &|e: &T| e.clone(), false)
+}
+
+/// It takes a function that extracts a [`RadixKey`] from each element,
+/// and sorts it in ascending order of the keys using a spread sort algorithm.
+///
+/// ```rust
+/// use buldak::spread;
+///
+/// let mut readings = [("b", 0.5), ("a", -2.0), ("c", 0.25)];
+/// spread::sort_by_key(&mut readings, |e| e.1);
+/// assert_eq!(readings, [("a", -2.0), ("c", 0.25), ("b", 0.5)]);
+/// ```
+pub fn sort_by_key<T, K, F>(array: &mut [T], key: F)
+where
+    T: std::clone::Clone,
+    K: RadixKey,
+    F: Fn(&T) -> K,
+{
+    _spread_sort_impl(array, &key, true)
+}
+
+/// It takes a function that extracts a [`RadixKey`] from each element,
+/// and sorts it in descending order of the keys using a spread sort algorithm.
+///
+/// ```rust
+/// use buldak::spread;
+///
+/// let mut readings = [("b", 0.5), ("a", -2.0), ("c", 0.25)];
+/// spread::sort_by_key_reverse(&mut readings, |e| e.1);
+/// assert_eq!(readings, [("b", 0.5), ("c", 0.25), ("a", -2.0)]);
+/// ```
+pub fn sort_by_key_reverse<T, K, F>(array: &mut [T], key: F)
+where
+    T: std::clone::Clone,
+    K: RadixKey,
+    F: Fn(&T) -> K,
+{
+    _spread_sort_impl(array, &key, false)
+}
+
+/// Sort strings in ascending order using a spread sort algorithm.
+///
+/// ```rust
+/// use buldak::spread;
+///
+/// let mut words = ["banana", "apple", "cherry", "app", ""];
+/// spread::sort_strings(&mut words);
+/// assert_eq!(words, ["", "app", "apple", "banana", "cherry"]);
+/// ```
+pub fn sort_strings<T>(array: &mut [T])
+where
+    T: std::convert::AsRef<[u8]> + std::clone::Clone,
+{
+    _string_spread_sort_impl(array, &|e: &T| e.as_ref(), true)
+}
+
+/// Sort strings in descending order using a spread sort algorithm.
+///
+/// ```rust
+/// use buldak::spread;
+///
+/// let mut words = ["banana", "apple", "cherry", "app", ""];
+/// spread::sort_strings_reverse(&mut words);
+/// assert_eq!(words, ["cherry", "banana", "apple", "app", ""]);
+/// ```
+pub fn sort_strings_reverse<T>(array: &mut [T])
+where
+    T: std::convert::AsRef<[u8]> + std::clone::Clone,
+{
+    _string_spread_sort_impl(array, &|e: &T| e.as_ref(), false)
+}
+
+/// It takes a function that borrows a string key from each element,
+/// and sorts it in ascending order of the keys using a spread sort algorithm.
+///
+/// ```rust
+/// use buldak::spread;
+///
+/// let mut users = [("lee", 24), ("kim", 31), ("choi", 19)];
+/// spread::sort_strings_by_key(&mut users, |e| e.0);
+/// assert_eq!(users, [("choi", 19), ("kim", 31), ("lee", 24)]);
+/// ```
+pub fn sort_strings_by_key<T, K, F>(array: &mut [T], key: F)
+where
+    T: std::clone::Clone,
+    K: std::convert::AsRef<[u8]> + ?Sized + 'static,
+    F: Fn(&T) -> &K,
+{
+    _string_spread_sort_impl(array, &|e: &T| key(e).as_ref(), true)
+}
+
+/// It takes a function that borrows a string key from each element,
+/// and sorts it in descending order of the keys using a spread sort algorithm.
+///
+/// ```rust
+/// use buldak::spread;
+///
+/// let mut users = [("lee", 24), ("kim", 31), ("choi", 19)];
+/// spread::sort_strings_by_key_reverse(&mut users, |e| e.0);
+/// assert_eq!(users, [("lee", 24), ("kim", 31), ("choi", 19)]);
+/// ```
+pub fn sort_strings_by_key_reverse<T, K, F>(array: &mut [T], key: F)
+where
+    T: std::clone::Clone,
+    K: std::convert::AsRef<[u8]> + ?Sized + 'static,
+    F: Fn(&T) -> &K,
+{
+    _string_spread_sort_impl(array, &|e: &T| key(e).as_ref(), false)
+}
+
+// The 16 most significant bytes of the key, as one number.
+fn _prefix<K>(key: &K) -> u128
+where
+    K: RadixKey,
+{
+    let low = K::BYTES.saturating_sub(16);
+    (low..K::BYTES)
+        .rev()
+        .fold(0, |prefix, index| (prefix << 8) | key.byte(index) as u128)
+}
+
+fn _compare_keys<K>(l: &K, r: &K) -> std::cmp::Ordering
+where
+    K: RadixKey,
+{
+    _prefix(l).cmp(&_prefix(r)).then_with(|| {
+        (0..K::BYTES.saturating_sub(16))
+            .rev()
+            .map(|index| l.byte(index).cmp(&r.byte(index)))
+            .find(|order| *order != std::cmp::Ordering::Equal)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    })
+}
+
+// The fewest elements a range of keys 2^log_range wide needs for splitting it to beat
+// an intro sort, as in Boost's get_min_count: every split takes a pass over the range,
+// and a wide range of few elements needs more splits than an intro sort needs comparisons.
+fn _min_split_len(log_range: u32) -> usize {
+    // below base_range, each split takes one bit more than the one before, up to MAX_SPLITS
+    let base_iterations = MAX_SPLITS - LOG_MIN_SPLIT_COUNT;
+    let base_range =
+        (base_iterations + 1) * (MAX_SPLITS + LOG_MIN_SPLIT_COUNT) / 2 + LOG_MEAN_BIN_SIZE;
+
+    let log_len = if log_range < base_range {
+        let mut splits = LOG_MIN_SPLIT_COUNT;
+        let mut offset = LOG_MEAN_BIN_SIZE + LOG_MIN_SPLIT_COUNT;
+        while offset < log_range {
+            splits += 1;
+            offset += splits;
+        }
+        splits + LOG_MEAN_BIN_SIZE
+    } else {
+        // the rest of the range is split MAX_SPLITS bits at a time
+        let passes = (log_range - base_range).div_ceil(MAX_SPLITS);
+        passes + base_iterations + LOG_MEAN_BIN_SIZE + LOG_MIN_SPLIT_COUNT
+    };
+
+    // at most 22 for a 128-bit range
+    1 << log_len
+}
+
+// Moves every element of `array` into its bin, given the size of every bin.
+fn _permute<T, B>(array: &mut [T], counts: &[usize], bin_of: B)
+where
+    B: Fn(&T) -> usize,
+{
+    let mut heads = Vec::with_capacity(counts.len());
+    let mut tails = Vec::with_capacity(counts.len());
+    let mut total = 0;
+    for count in counts {
+        heads.push(total);
+        total += count;
+        tails.push(total);
+    }
+
+    for bin in 0..counts.len() {
+        while heads[bin] < tails[bin] {
+            let target = bin_of(&array[heads[bin]]);
+            if target == bin {
+                heads[bin] += 1;
+            } else {
+                utils::swap(array, heads[bin], heads[target]);
+                heads[target] += 1;
+            }
+        }
+    }
+}
+
+fn _spread_sort_impl<T, K, F>(array: &mut [T], key: &F, asc: bool)
+where
+    T: std::clone::Clone,
+    K: RadixKey,
+    F: Fn(&T) -> K,
+{
+    let compare = |l: &T, r: &T| {
+        let order = _compare_keys(&key(l), &key(r));
+        if asc {
+            order
+        } else {
+            order.reverse()
+        }
+    };
+
+    let mut stack = vec![(0, array.len())];
+
+    while let Some((begin, end)) = stack.pop() {
+        let range = &mut array[begin..end];
+
+        if range.len() <= MIN_SORT_SIZE {
+            crate::intro::sort_by(range, compare);
+            continue;
+        }
+
+        let prefixes = range.iter().map(|e| _prefix(&key(e)));
+        let (min, max) = prefixes.fold((u128::MAX, 0), |(min, max), p| {
+            (std::cmp::min(min, p), std::cmp::max(max, p))
+        });
+
+        if min == max {
+            // the prefixes can't tell longer keys apart
+            if K::BYTES > 16 {
+                crate::intro::sort_by(range, compare);
+            }
+            continue;
+        }
+
+        let log_range = u128::BITS - (max - min).leading_zeros();
+        if range.len() < _min_split_len(log_range) {
+            // too few elements for how widely the keys are spread
+            crate::intro::sort_by(range, compare);
+            continue;
+        }
+
+        // as many bins as the length allows, but no more than there are distinct prefixes
+        let log_len = usize::BITS - 1 - range.len().leading_zeros();
+        let bits = log_len
+            .saturating_sub(LOG_MEAN_BIN_SIZE)
+            .clamp(1, MAX_SPLITS)
+            .min(log_range);
+        let shift = log_range - bits;
+        let bins = 1usize << bits;
+
+        let bin_of = |e: &T| {
+            let bin = ((_prefix(&key(e)) - min) >> shift) as usize;
+            if asc {
+                bin
+            } else {
+                bins - 1 - bin
+            }
+        };
+
+        let mut counts = vec![0usize; bins];
+        for e in range.iter() {
+            counts[bin_of(e)] += 1;
+        }
+
+        _permute(range, &counts, bin_of);
+
+        let mut bin_begin = begin;
+        for count in counts {
+            if count > 1 {
+                stack.push((bin_begin, bin_begin + count));
+            }
+            bin_begin += count;
+        }
+    }
+}
+
+// The common prefix of a range is skipped, then the range is split on the next byte.
+fn _string_spread_sort_impl<T, F>(array: &mut [T], key: &F, asc: bool)
+where
+    T: std::clone::Clone,
+    F: Fn(&T) -> &[u8],
+{
+    let mut stack = vec![(0, array.len(), 0)];
+
+    while let Some((begin, end, mut depth)) = stack.pop() {
+        let range = &mut array[begin..end];
+
+        if range.len() <= MIN_SORT_SIZE {
+            crate::intro::sort_by(range, |l, r| {
+                let (l, r) = (key(l), key(r));
+                let order =
+                    l[std::cmp::min(depth, l.len())..].cmp(&r[std::cmp::min(depth, r.len())..]);
+                if asc {
+                    order
+                } else {
+                    order.reverse()
+                }
+            });
+            continue;
+        }
+
+        let first = key(&range[0]);
+        loop {
+            let digit = _digit(first, depth);
+            if digit == 0 {
+                break;
+            }
+            if range.iter().any(|e| _digit(key(e), depth) != digit) {
+                break;
+            }
+            depth += 1;
+        }
+
+        let bin_of = |e: &T| {
+            let digit = _digit(key(e), depth);
+            if asc {
+                digit
+            } else {
+                DIGITS - 1 - digit
+            }
+        };
+
+        let mut counts = vec![0usize; DIGITS];
+        for e in range.iter() {
+            counts[bin_of(e)] += 1;
+        }
+        if counts.contains(&range.len()) {
+            // every key ended at the same place
+            continue;
+        }
+
+        _permute(range, &counts, bin_of);
+
+        let mut bin_begin = begin;
+        for (bin, count) in counts.into_iter().enumerate() {
+            let ended = if asc { bin == 0 } else { bin == DIGITS - 1 };
+            if count > 1 && !ended {
+                stack.push((bin_begin, bin_begin + count, depth + 1));
+            }
+            bin_begin += count;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn sort_ascending() {
+        struct TestCase {
+            input: Vec<i32>,
+            expected: Vec<i32>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                input: vec![1, 4, 2, 3, 5, 111, 234, 21, 13],
+                expected: vec![1, 2, 3, 4, 5, 13, 21, 111, 234],
+            },
+            TestCase {
+                input: vec![],
+                expected: vec![],
+            },
+        ];
+
+        for case in test_cases {
+            let mut actual = case.input.clone();
+            super::sort(&mut actual);
+            assert_eq!(actual, case.expected);
+        }
+    }
+
+    #[test]
+    fn sort_descending() {
+        struct TestCase {
+            input: Vec<i32>,
+            expected: Vec<i32>,
+        }
+
+        let test_cases = vec![TestCase {
+            input: vec![1, 4, 2, 3, 5, 111, 234, 21, 13],
+            expected: vec![234, 111, 21, 13, 5, 4, 3, 2, 1],
+        }];
+
+        for case in test_cases {
+            let mut actual = case.input.clone();
+            super::sort_reverse(&mut actual);
+            assert_eq!(actual, case.expected);
+        }
+    }
+
+    #[test]
+    fn sort_skewed() {
+        assert_eq!(super::_min_split_len(8), 1 << 11);
+        assert_eq!(super::_min_split_len(32), 1 << 13);
+        assert_eq!(super::_min_split_len(64), 1 << 16);
+        assert_eq!(super::_min_split_len(128), 1 << 22);
+
+        // most keys are tiny, and a few are spread over the whole range
+        for len in [300, 5_000, 70_000] {
+            let input: Vec<u64> = (0..len as u64)
+                .map(|i| {
+                    let x = i.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+                    x >> (x % 64)
+                })
+                .collect();
+
+            let mut expected = input.clone();
+            expected.sort();
+            let mut actual = input.clone();
+            super::sort(&mut actual);
+            assert_eq!(actual, expected);
+
+            expected.reverse();
+            let mut actual = input;
+            super::sort_reverse(&mut actual);
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn sort_large() {
+        // skewed: most values are close together, a few are far away
+        let ints: Vec<i64> = (0..20_000i64)
+            .map(|i| match i % 100 {
+                0 => i64::MIN + i,
+                1 => i64::MAX - i,
+                _ => (i * 7919) % 1013,
+            })
+            .collect();
+        let mut expected = ints.clone();
+        expected.sort();
+        let mut actual = ints.clone();
+        super::sort(&mut actual);
+        assert_eq!(actual, expected);
+
+        let wide: Vec<(u128, u64)> = ints.iter().map(|&i| (7, i as u64 % 1000)).collect();
+        let mut expected = wide.clone();
+        expected.sort_by(|l, r| r.cmp(l));
+        let mut actual = wide;
+        super::sort_reverse(&mut actual);
+        assert_eq!(actual, expected);
+
+        let floats: Vec<f64> = ints.iter().map(|&i| i as f64 / 3.0).collect();
+        let mut expected = floats.clone();
+        expected.sort_by(|l, r| l.total_cmp(r));
+        let mut actual = floats;
+        super::sort(&mut actual);
+        assert_eq!(actual, expected);
+
+        let strings: Vec<String> = ints.iter().map(|i| format!("/log/{}", i % 3001)).collect();
+        let mut expected = strings.clone();
+        expected.sort();
+        let mut actual = strings.clone();
+        super::sort_strings(&mut actual);
+        assert_eq!(actual, expected);
+
+        expected.reverse();
+        let mut actual = strings;
+        super::sort_strings_reverse(&mut actual);
+        assert_eq!(actual, expected);
+    }
+}
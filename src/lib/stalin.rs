@@ -2,7 +2,19 @@
 //!
 //! Purge all unsorted elements and make them sorted.
 //!
-//! O(N)
+//! O(N), or O(Nlog₂N) to purge as few elements as possible
+
+/// Which elements are purged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Purge {
+    /// Every element smaller than the last kept one, in one pass.
+    Greedy,
+    /// As few elements as possible, keeping a longest sorted subsequence.
+    Minimal,
+}
+
+/// The kept and the purged elements, each with its index in the original array.
+pub type Partition<T> = (Vec<(usize, T)>, Vec<(usize, T)>);
 
 /// Sort in ascending order using a stalin sort algorithm.
 ///
@@ -15,7 +27,7 @@
 /// ```
 pub fn sort<T>(array: &mut Vec<T>)
 where
-    T: std::cmp::Ord + std::clone::Clone,
+    T: std::cmp::Ord,
 {
     sort_by(array, |l, r| l.cmp(r))
 }
@@ -31,7 +43,7 @@ where
 /// ```
 pub fn sort_reverse<T>(array: &mut Vec<T>)
 where
-    T: std::cmp::Ord + std::clone::Clone,
+    T: std::cmp::Ord,
 {
     sort_by(array, |l, r| l.cmp(r).reverse())
 }
@@ -48,31 +60,171 @@ where
 /// ```
 pub fn sort_by<T, F>(array: &mut Vec<T>, compare: F)
 where
-    T: std::cmp::Ord + std::clone::Clone,
     F: Fn(&T, &T) -> std::cmp::Ordering,
 {
-    _stalin_sort_impl(array, compare)
+    _stalin_sort_impl(array, Purge::Greedy, compare)
 }
 
-fn _stalin_sort_impl<T, F>(array: &mut Vec<T>, compare: F)
+/// Sort in ascending order using a stalin sort algorithm
+/// that purges as few elements as possible.
+///
+/// ```rust
+/// use buldak::stalin;
+///
+/// let mut nums = vec![1, 4, 2, 3, 5, 11, 23, 21, 13, 0];
+/// stalin::sort_minimal(&mut nums);
+/// assert_eq!(nums, vec![1, 2, 3, 5, 11, 13]);
+/// ```
+pub fn sort_minimal<T>(array: &mut Vec<T>)
+where
+    T: std::cmp::Ord,
+{
+    sort_minimal_by(array, |l, r| l.cmp(r))
+}
+
+/// It takes a comparator function to determine the order,
+/// and sorts it using a stalin sort algorithm that purges as few elements as possible.
+///
+/// ```rust
+/// use buldak::stalin;
+///
+/// let mut nums = vec![1, 4, 2, 3, 5, 11, 23, 21, 13, 0];
+/// stalin::sort_minimal_by(&mut nums, |l, r| l.cmp(r).reverse());
+/// assert_eq!(nums, vec![23, 21, 13, 0]);
+/// ```
+pub fn sort_minimal_by<T, F>(array: &mut Vec<T>, compare: F)
 where
-    T: std::cmp::Ord + std::clone::Clone,
     F: Fn(&T, &T) -> std::cmp::Ordering,
 {
-    let mut prev = &array[0];
-    *array = array
-        .iter()
-        .filter(|&e| {
-            let cmp = compare(prev, e);
-            if cmp != std::cmp::Ordering::Greater {
-                prev = e;
-                true
-            } else {
-                false
-            }
-        })
-        .cloned()
-        .collect();
+    _stalin_sort_impl(array, Purge::Minimal, compare)
+}
+
+/// Sort in ascending order using a stalin sort algorithm,
+/// and returns the kept and the purged elements with their original indices.
+///
+/// ```rust
+/// use buldak::stalin;
+///
+/// let nums = vec![1, 4, 2, 3, 5];
+///
+/// let (kept, purged) = stalin::sort_partition(nums.clone(), stalin::Purge::Greedy);
+/// assert_eq!(kept, vec![(0, 1), (1, 4), (4, 5)]);
+/// assert_eq!(purged, vec![(2, 2), (3, 3)]);
+///
+/// let (kept, purged) = stalin::sort_partition(nums, stalin::Purge::Minimal);
+/// assert_eq!(kept, vec![(0, 1), (2, 2), (3, 3), (4, 5)]);
+/// assert_eq!(purged, vec![(1, 4)]);
+/// ```
+pub fn sort_partition<T>(array: Vec<T>, purge: Purge) -> Partition<T>
+where
+    T: std::cmp::Ord,
+{
+    sort_partition_by(array, purge, |l, r| l.cmp(r))
+}
+
+/// It takes a comparator function to determine the order,
+/// sorts it using a stalin sort algorithm,
+/// and returns the kept and the purged elements with their original indices.
+///
+/// ```rust
+/// use buldak::stalin;
+///
+/// let readings = vec![("a", 3), ("b", 1), ("c", 2)];
+/// let (kept, purged) =
+///     stalin::sort_partition_by(readings, stalin::Purge::Minimal, |l, r| l.1.cmp(&r.1));
+/// assert_eq!(kept, vec![(1, ("b", 1)), (2, ("c", 2))]);
+/// assert_eq!(purged, vec![(0, ("a", 3))]);
+/// ```
+pub fn sort_partition_by<T, F>(array: Vec<T>, purge: Purge, compare: F) -> Partition<T>
+where
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    let keep = _keep_mask(&array, purge, compare);
+
+    let mut kept = vec![];
+    let mut purged = vec![];
+    for (index, e) in array.into_iter().enumerate() {
+        if keep[index] {
+            kept.push((index, e));
+        } else {
+            purged.push((index, e));
+        }
+    }
+
+    (kept, purged)
+}
+
+// The kept elements are swapped to the front, and the rest is truncated.
+fn _stalin_sort_impl<T, F>(array: &mut Vec<T>, purge: Purge, compare: F)
+where
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    let keep = _keep_mask(array, purge, compare);
+
+    let mut kept = 0;
+    for (index, keep) in keep.into_iter().enumerate() {
+        if keep {
+            array.swap(kept, index);
+            kept += 1;
+        }
+    }
+    array.truncate(kept);
+}
+
+fn _keep_mask<T, F>(array: &[T], purge: Purge, compare: F) -> Vec<bool>
+where
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    match purge {
+        Purge::Greedy => {
+            let mut last: Option<&T> = None;
+            array
+                .iter()
+                .map(|e| match last {
+                    Some(prev) if compare(prev, e) == std::cmp::Ordering::Greater => false,
+                    _ => {
+                        last = Some(e);
+                        true
+                    }
+                })
+                .collect()
+        }
+        Purge::Minimal => _longest_sorted_subsequence(array, compare),
+    }
+}
+
+// Patience sorting: tails[k] is the index of the smallest last element
+// of a sorted subsequence of length k + 1 found so far.
+fn _longest_sorted_subsequence<T, F>(array: &[T], compare: F) -> Vec<bool>
+where
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    let mut tails: Vec<usize> = vec![];
+    let mut previous = vec![usize::MAX; array.len()];
+
+    for (index, e) in array.iter().enumerate() {
+        // equal elements may follow each other, so look for the first tail greater than e
+        let pile =
+            tails.partition_point(|&tail| compare(&array[tail], e) != std::cmp::Ordering::Greater);
+
+        if pile > 0 {
+            previous[index] = tails[pile - 1];
+        }
+        if pile == tails.len() {
+            tails.push(index);
+        } else {
+            tails[pile] = index;
+        }
+    }
+
+    let mut keep = vec![false; array.len()];
+    let mut index = tails.last().copied().unwrap_or(usize::MAX);
+    while index != usize::MAX {
+        keep[index] = true;
+        index = previous[index];
+    }
+
+    keep
 }
 
 mod tests {
@@ -113,4 +265,25 @@ mod tests {
             assert_eq!(actual, case.expected);
         }
     }
+
+    #[test]
+    fn sort_partition() {
+        let input = vec![5, 1, 2, 2, 9, 3, 4, 0, 8];
+
+        let (kept, purged) = super::sort_partition(input.clone(), super::Purge::Greedy);
+        assert_eq!(kept, vec![(0, 5), (4, 9)]);
+        assert_eq!(purged.len(), input.len() - 2);
+
+        let (kept, purged) = super::sort_partition(input.clone(), super::Purge::Minimal);
+        assert_eq!(kept, vec![(1, 1), (2, 2), (3, 2), (5, 3), (6, 4), (8, 8)]);
+        assert_eq!(purged, vec![(0, 5), (4, 9), (7, 0)]);
+
+        let mut actual = input;
+        super::sort_minimal(&mut actual);
+        assert_eq!(actual, vec![1, 2, 2, 3, 4, 8]);
+
+        let mut actual: Vec<String> = vec![];
+        super::sort(&mut actual);
+        assert!(actual.is_empty());
+    }
 }
@@ -51,33 +51,33 @@ where
     T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
 {
-    _stooge_sort_impl(array, compare);
+    _stooge_sort_impl(array, compare, utils::swap);
 }
 
-fn _stooge_sort_impl<T, F>(array: &mut [T], compare: F)
+pub(crate) fn _stooge_sort_impl<T, F, S>(array: &mut [T], compare: F, mut swap: S)
 where
-    T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
+    S: FnMut(&mut [T], usize, usize),
 {
-    _stooge_sort_recursive(array, 0, array.len() - 1, compare);
+    _stooge_sort_recursive(array, 0, array.len() - 1, compare, &mut swap);
 }
 
-fn _stooge_sort_recursive<T, F>(array: &mut [T], i: usize, j: usize, compare: F)
+fn _stooge_sort_recursive<T, F, S>(array: &mut [T], i: usize, j: usize, compare: F, swap: &mut S)
 where
-    T: std::cmp::Ord,
     F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
+    S: FnMut(&mut [T], usize, usize),
 {
     // If the leftmost element is larger than the rightmost element
     if compare(&array[i], &array[j]) == std::cmp::Ordering::Greater {
-        utils::swap(array, i, j);
+        swap(array, i, j);
     }
 
     // If there are at least 3 elements in the array
     if j - i + 1 > 2 {
         let k = (j - i + 1) / 3;
-        _stooge_sort_recursive(array, i, j - k, compare.clone()); // Sort the first 2/3 of the array
-        _stooge_sort_recursive(array, i + k, j, compare.clone()); // Sort the last 2/3 of the array
-        _stooge_sort_recursive(array, i, j - k, compare); // Sort the first 2/3 of the array again
+        _stooge_sort_recursive(array, i, j - k, compare.clone(), swap); // Sort the first 2/3 of the array
+        _stooge_sort_recursive(array, i + k, j, compare.clone(), swap); // Sort the last 2/3 of the array
+        _stooge_sort_recursive(array, i, j - k, compare, swap); // Sort the first 2/3 of the array again
     }
 }
 
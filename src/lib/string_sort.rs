@@ -0,0 +1,418 @@
+//! string sort algorithms.
+//!
+//! Multikey quicksort and burstsort look at one character position at a time
+//! instead of comparing whole strings, so common prefixes are only read once.
+//!
+//! unstable sort
+//! **O(N log₂N + D)**: D=total length of the distinguishing prefixes
+
+mod utils;
+
+use crate::msd_radix::{_digit, _insertion_sort, DIGITS};
+
+/// Ranges with at most this many elements are sorted with an insertion sort.
+const INSERTION_THRESHOLD: usize = 16;
+
+/// A burst trie bucket is turned into a node once it holds more than this many strings.
+const BURST_LIMIT: usize = 1024;
+
+/// Sort in ascending order using a multikey quicksort algorithm.
+///
+/// ```rust
+/// use buldak::string_sort;
+///
+/// let mut paths = ["/var/log", "/usr/bin", "/var", "/usr/lib", "/etc"];
+/// string_sort::sort(&mut paths);
+/// assert_eq!(paths, ["/etc", "/usr/bin", "/usr/lib", "/var", "/var/log"]);
+/// ```
+pub fn sort<T>(array: &mut [T])
+where
+    T: std::convert::AsRef<[u8]>,
+{
+    _multikey_quick_sort_impl(array, &|e: &T| e.as_ref(), 0, true)
+}
+
+/// Sort in descending order using a multikey quicksort algorithm.
+///
+/// ```rust
+/// use buldak::string_sort;
+///
+/// let mut paths = ["/var/log", "/usr/bin", "/var", "/usr/lib", "/etc"];
+/// string_sort::sort_reverse(&mut paths);
+/// assert_eq!(paths, ["/var/log", "/var", "/usr/lib", "/usr/bin", "/etc"]);
+/// ```
+pub fn sort_reverse<T>(array: &mut [T])
+where
+    T: std::convert::AsRef<[u8]>,
+{
+    _multikey_quick_sort_impl(array, &|e: &T| e.as_ref(), 0, false)
+}
+
+/// It takes a function that borrows a string key from each element,
+/// and sorts it in ascending order of the keys using a multikey quicksort algorithm.
+///
+/// ```rust
+/// use buldak::string_sort;
+///
+/// let mut requests = [("/b", 200), ("/a", 404), ("/ab", 200)];
+/// string_sort::sort_by_key(&mut requests, |e| e.0);
+/// assert_eq!(requests, [("/a", 404), ("/ab", 200), ("/b", 200)]);
+/// ```
+pub fn sort_by_key<T, K, F>(array: &mut [T], key: F)
+where
+    K: std::convert::AsRef<[u8]> + ?Sized + 'static,
+    F: Fn(&T) -> &K,
+{
+    _multikey_quick_sort_impl(array, &|e: &T| key(e).as_ref(), 0, true)
+}
+
+/// It takes a function that borrows a string key from each element,
+/// and sorts it in descending order of the keys using a multikey quicksort algorithm.
+///
+/// ```rust
+/// use buldak::string_sort;
+///
+/// let mut requests = [("/b", 200), ("/a", 404), ("/ab", 200)];
+/// string_sort::sort_by_key_reverse(&mut requests, |e| e.0);
+/// assert_eq!(requests, [("/b", 200), ("/ab", 200), ("/a", 404)]);
+/// ```
+pub fn sort_by_key_reverse<T, K, F>(array: &mut [T], key: F)
+where
+    K: std::convert::AsRef<[u8]> + ?Sized + 'static,
+    F: Fn(&T) -> &K,
+{
+    _multikey_quick_sort_impl(array, &|e: &T| key(e).as_ref(), 0, false)
+}
+
+/// Sort in ascending order using a burstsort algorithm.
+///
+/// ```rust
+/// use buldak::string_sort;
+///
+/// let mut urls = vec![
+///     "https://b.com".to_string(),
+///     "https://a.com/x".to_string(),
+///     "https://a.com".to_string(),
+/// ];
+/// string_sort::burst_sort(&mut urls);
+/// assert_eq!(urls, ["https://a.com", "https://a.com/x", "https://b.com"]);
+/// ```
+pub fn burst_sort<T>(array: &mut [T])
+where
+    T: std::convert::AsRef<[u8]>,
+{
+    _burst_sort_impl(array, |e| e.as_ref(), true)
+}
+
+/// Sort in descending order using a burstsort algorithm.
+///
+/// ```rust
+/// use buldak::string_sort;
+///
+/// let mut urls = vec![
+///     "https://b.com".to_string(),
+///     "https://a.com/x".to_string(),
+///     "https://a.com".to_string(),
+/// ];
+/// string_sort::burst_sort_reverse(&mut urls);
+/// assert_eq!(urls, ["https://b.com", "https://a.com/x", "https://a.com"]);
+/// ```
+pub fn burst_sort_reverse<T>(array: &mut [T])
+where
+    T: std::convert::AsRef<[u8]>,
+{
+    _burst_sort_impl(array, |e| e.as_ref(), false)
+}
+
+/// It takes a function that borrows a string key from each element,
+/// and sorts it in ascending order of the keys using a burstsort algorithm.
+///
+/// ```rust
+/// use buldak::string_sort;
+///
+/// let mut requests = [("/b", 200), ("/a", 404), ("/ab", 200)];
+/// string_sort::burst_sort_by_key(&mut requests, |e| e.0);
+/// assert_eq!(requests, [("/a", 404), ("/ab", 200), ("/b", 200)]);
+/// ```
+pub fn burst_sort_by_key<T, K, F>(array: &mut [T], key: F)
+where
+    K: std::convert::AsRef<[u8]> + ?Sized + 'static,
+    F: Fn(&T) -> &K,
+{
+    _burst_sort_impl(array, |e| key(e).as_ref(), true)
+}
+
+/// It takes a function that borrows a string key from each element,
+/// and sorts it in descending order of the keys using a burstsort algorithm.
+///
+/// ```rust
+/// use buldak::string_sort;
+///
+/// let mut requests = [("/b", 200), ("/a", 404), ("/ab", 200)];
+/// string_sort::burst_sort_by_key_reverse(&mut requests, |e| e.0);
+/// assert_eq!(requests, [("/b", 200), ("/ab", 200), ("/a", 404)]);
+/// ```
+pub fn burst_sort_by_key_reverse<T, K, F>(array: &mut [T], key: F)
+where
+    K: std::convert::AsRef<[u8]> + ?Sized + 'static,
+    F: Fn(&T) -> &K,
+{
+    _burst_sort_impl(array, |e| key(e).as_ref(), false)
+}
+
+// Bentley and Sedgewick's 3-way partition on the character at `depth`.
+// Only the middle part moves on to the next character.
+fn _multikey_quick_sort_impl<T, F>(array: &mut [T], key: &F, depth: usize, asc: bool)
+where
+    F: Fn(&T) -> &[u8],
+{
+    let mut stack = vec![(0, array.len(), depth)];
+
+    while let Some((begin, end, depth)) = stack.pop() {
+        if end - begin <= INSERTION_THRESHOLD {
+            _insertion_sort(&mut array[begin..end], key, depth, asc);
+            continue;
+        }
+
+        let digit = |e: &T| _digit(key(e), depth);
+        let pivot = _median_of_three(
+            digit(&array[begin]),
+            digit(&array[begin + (end - begin) / 2]),
+            digit(&array[end - 1]),
+        );
+
+        let mut less = begin;
+        let mut i = begin;
+        let mut greater = end;
+
+        while i < greater {
+            let order = digit(&array[i]).cmp(&pivot);
+            let order = if asc { order } else { order.reverse() };
+
+            match order {
+                std::cmp::Ordering::Less => {
+                    utils::swap(array, less, i);
+                    less += 1;
+                    i += 1;
+                }
+                std::cmp::Ordering::Greater => {
+                    greater -= 1;
+                    utils::swap(array, i, greater);
+                }
+                std::cmp::Ordering::Equal => i += 1,
+            }
+        }
+
+        stack.push((begin, less, depth));
+        stack.push((greater, end, depth));
+        if pivot != 0 {
+            stack.push((less, greater, depth + 1));
+        }
+    }
+}
+
+fn _median_of_three(a: usize, b: usize, c: usize) -> usize {
+    std::cmp::max(std::cmp::min(a, b), std::cmp::min(std::cmp::max(a, b), c))
+}
+
+enum BurstChild<'a> {
+    Bucket(Vec<(&'a [u8], usize)>),
+    Node(Box<BurstNode<'a>>),
+}
+
+struct BurstNode<'a> {
+    children: Vec<BurstChild<'a>>,
+}
+
+impl<'a> BurstNode<'a> {
+    fn new() -> Self {
+        BurstNode {
+            children: (0..DIGITS).map(|_| BurstChild::Bucket(vec![])).collect(),
+        }
+    }
+}
+
+// Strings are inserted into a trie whose leaves are small buckets.
+// A bucket that grows too large bursts into a new node one character deeper.
+// The buckets are then sorted with multikey quicksort in the order of the trie.
+fn _burst_sort_impl<T, F>(array: &mut [T], key: F, asc: bool)
+where
+    F: Fn(&T) -> &[u8],
+{
+    let mut order = {
+        let mut root = BurstNode::new();
+
+        for (index, e) in array.iter().enumerate() {
+            _burst_insert(&mut root, key(e), index);
+        }
+
+        let mut order = Vec::with_capacity(array.len());
+        // (children, how many of them were visited, depth of the node)
+        let mut stack = vec![(root.children, 0, 0)];
+
+        while let Some((children, visited, depth)) = stack.last_mut() {
+            if *visited == DIGITS {
+                stack.pop();
+                continue;
+            }
+
+            let digit = if asc { *visited } else { DIGITS - 1 - *visited };
+            let depth = *depth;
+            *visited += 1;
+
+            match std::mem::replace(&mut children[digit], BurstChild::Bucket(vec![])) {
+                BurstChild::Bucket(mut bucket) => {
+                    // keys that ended at this node are all equal
+                    if digit != 0 {
+                        _multikey_quick_sort_impl(&mut bucket, &|e| e.0, depth + 1, asc);
+                    }
+                    order.extend(bucket.into_iter().map(|e| e.1));
+                }
+                BurstChild::Node(child) => stack.push((child.children, 0, depth + 1)),
+            }
+        }
+
+        order
+    };
+
+    _apply_order(array, &mut order);
+}
+
+fn _burst_insert<'a>(root: &mut BurstNode<'a>, key: &'a [u8], index: usize) {
+    let mut node = root;
+    let mut depth = 0;
+
+    loop {
+        let digit = _digit(key, depth);
+
+        if let BurstChild::Node(_) = node.children[digit] {
+            node = match &mut node.children[digit] {
+                BurstChild::Node(child) => child,
+                BurstChild::Bucket(_) => unreachable!(),
+            };
+            depth += 1;
+            continue;
+        }
+
+        let full = match &mut node.children[digit] {
+            BurstChild::Bucket(bucket) => {
+                bucket.push((key, index));
+
+                // keys that ended here can't be split any further
+                if digit == 0 || bucket.len() <= BURST_LIMIT {
+                    return;
+                }
+                std::mem::take(bucket)
+            }
+            BurstChild::Node(_) => unreachable!(),
+        };
+
+        let mut child = BurstNode::new();
+        for (key, index) in full {
+            if let BurstChild::Bucket(bucket) = &mut child.children[_digit(key, depth + 1)] {
+                bucket.push((key, index));
+            }
+        }
+        node.children[digit] = BurstChild::Node(Box::new(child));
+        return;
+    }
+}
+
+// Moves the element at `order[i]` to `i`, following the cycles of the permutation.
+fn _apply_order<T>(array: &mut [T], order: &mut [usize]) {
+    for start in 0..order.len() {
+        let mut current = start;
+
+        while order[current] != usize::MAX {
+            let next = order[current];
+            order[current] = usize::MAX;
+            if next == start {
+                break;
+            }
+            utils::swap(array, current, next);
+            current = next;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn sort_ascending() {
+        struct TestCase {
+            input: Vec<&'static str>,
+            expected: Vec<&'static str>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                input: vec!["/var/log", "/usr/bin", "/var", "/usr/lib", "/etc", ""],
+                expected: vec!["", "/etc", "/usr/bin", "/usr/lib", "/var", "/var/log"],
+            },
+            TestCase {
+                input: vec![],
+                expected: vec![],
+            },
+        ];
+
+        for case in test_cases {
+            let mut actual = case.input.clone();
+            super::sort(&mut actual);
+            assert_eq!(actual, case.expected);
+
+            let mut actual = case.input.clone();
+            super::burst_sort(&mut actual);
+            assert_eq!(actual, case.expected);
+        }
+    }
+
+    #[test]
+    fn sort_descending() {
+        struct TestCase {
+            input: Vec<&'static str>,
+            expected: Vec<&'static str>,
+        }
+
+        let test_cases = vec![TestCase {
+            input: vec!["/var/log", "/usr/bin", "/var", "/usr/lib", "/etc", ""],
+            expected: vec!["/var/log", "/var", "/usr/lib", "/usr/bin", "/etc", ""],
+        }];
+
+        for case in test_cases {
+            let mut actual = case.input.clone();
+            super::sort_reverse(&mut actual);
+            assert_eq!(actual, case.expected);
+
+            let mut actual = case.input.clone();
+            super::burst_sort_reverse(&mut actual);
+            assert_eq!(actual, case.expected);
+        }
+    }
+
+    #[test]
+    fn sort_like_merge_and_tim() {
+        // enough shared prefixes for the burst trie to grow a few levels
+        let input: Vec<String> = (0..20_000u32)
+            .map(|i| format!("https://host{}/path/{}", i % 3, (i * 7919) % 4099))
+            .collect();
+
+        let mut expected = input.clone();
+        crate::merge::sort(&mut expected);
+        let mut tim = input.clone();
+        crate::tim::sort(&mut tim);
+        assert_eq!(tim, expected);
+
+        let mut actual = input.clone();
+        super::sort(&mut actual);
+        assert_eq!(actual, expected);
+
+        let mut actual = input.clone();
+        super::burst_sort(&mut actual);
+        assert_eq!(actual, expected);
+
+        expected.reverse();
+        let mut actual = input;
+        super::burst_sort_reverse(&mut actual);
+        assert_eq!(actual, expected);
+    }
+}
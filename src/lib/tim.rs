@@ -5,8 +5,6 @@
 
 // not impl
 
-use std::default::Default;
-
 /// Sort in ascending order using a tim sort algorithm.
 ///
 /// ```rust
@@ -18,7 +16,7 @@ use std::default::Default;
 /// ```
 pub fn sort<T>(array: &mut [T])
 where
-    T: std::cmp::Ord + std::clone::Clone + std::default::Default,
+    T: std::cmp::Ord + std::clone::Clone,
 {
     sort_by(array, |l, r| l.cmp(r))
 }
@@ -34,7 +32,7 @@ where
 /// ```
 pub fn sort_reverse<T>(array: &mut [T])
 where
-    T: std::cmp::Ord + std::clone::Clone + std::default::Default,
+    T: std::cmp::Ord + std::clone::Clone,
 {
     sort_by(array, |l, r| l.cmp(r).reverse())
 }
@@ -51,7 +49,7 @@ where
 /// ```
 pub fn sort_by<T, F>(array: &mut [T], compare: F)
 where
-    T: std::cmp::Ord + std::clone::Clone + std::default::Default,
+    T: std::clone::Clone,
     F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
 {
     _tim_sort_impl(array, compare)
@@ -63,7 +61,7 @@ const RUN: usize = 32;
 // array[0...n-1] (similar to merge sort)
 fn _tim_sort_impl<T, F>(array: &mut [T], compare: F)
 where
-    T: std::cmp::Ord + std::clone::Clone + std::default::Default,
+    T: std::clone::Clone,
     F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
 {
     // Sort individual subarrays of size RUN
@@ -103,7 +101,9 @@ where
 
             // merge sub array arr[left.....mid] &
             // arr[mid+1....right]
-            _merge(array, left, middle, right, compare.clone());
+            if middle < right {
+                _merge(array, left, middle, right, compare.clone());
+            }
 
             left += size * 2;
         }
@@ -113,30 +113,22 @@ where
 }
 
 // Merge function merges the sorted runs
-fn _merge<T, F>(array: &mut [T], left: usize, middle: usize, right: usize, compare: F)
+pub(crate) fn _merge<T, F>(array: &mut [T], left: usize, middle: usize, right: usize, compare: F)
 where
-    T: std::cmp::Ord + std::clone::Clone + std::default::Default,
+    T: std::clone::Clone,
     F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
 {
     // Original array is broken in two parts
     // left and right array
-    let left_len = middle - left + 1;
-    let right_len = right - middle;
+    let array_left: Vec<T> = array[left..=middle].to_vec();
+    let array_right: Vec<T> = array[(middle + 1)..=right].to_vec();
 
-    let mut array_left: Vec<T> = vec![Default::default(); left_len];
-    let mut array_right: Vec<T> = vec![Default::default(); right_len];
-
-    for i in 0..left_len {
-        array_left[i] = array[left + i].clone();
-    }
-
-    for i in 0..right_len {
-        array_right[i] = array[left + i + middle].clone();
-    }
+    let left_len = array_left.len();
+    let right_len = array_right.len();
 
     let mut i = 0; //left array index
     let mut j = 0; //right array index
-    let mut k = 1; //full array index
+    let mut k = left; //full array index
 
     // After comparing, we
     // merge those two array
@@ -171,7 +163,7 @@ where
 // to right index which is of size atmost RUN
 fn _insertion_sort<T, F>(array: &mut [T], left: usize, right: usize, compare: F)
 where
-    T: std::cmp::Ord + std::clone::Clone,
+    T: std::clone::Clone,
     F: Fn(&T, &T) -> std::cmp::Ordering + std::clone::Clone,
 {
     for i in (left + 1)..=right {
@@ -196,10 +188,16 @@ mod tests {
             expected: Vec<i32>,
         }
 
-        let test_cases = vec![TestCase {
-            input: vec![1, 4, 2, 3, 5, 111, 234, 21, 13],
-            expected: vec![1, 2, 3, 4, 5, 13, 21, 111, 234],
-        }];
+        let test_cases = vec![
+            TestCase {
+                input: vec![1, 4, 2, 3, 5, 111, 234, 21, 13],
+                expected: vec![1, 2, 3, 4, 5, 13, 21, 111, 234],
+            },
+            TestCase {
+                input: (0..100).rev().collect(),
+                expected: (0..100).collect(),
+            },
+        ];
 
         for case in test_cases {
             let mut actual = case.input.clone();
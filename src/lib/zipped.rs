@@ -0,0 +1,336 @@
+//! sorting parallel slices together.
+//!
+//! The sorts whose implementation only compares and swaps elements run on the keys directly,
+//! and every swap of two keys swaps the companion slices too.
+//! The other algorithms sort an argsort of the keys, and the permutation is applied
+//! to the keys and every companion slice by swapping along its cycles.
+//! Either way no tuples are built and unzipped.
+
+use crate::argsort::{Algorithm, Permute};
+
+/// A tuple of slices whose first slice holds the keys to sort by.
+pub trait Columns: Permute {
+    type Key;
+
+    /// The other slices.
+    type Rest<'a>: Permute
+    where
+        Self: 'a;
+
+    /// The slice sorted by.
+    fn keys(&self) -> &[Self::Key];
+
+    /// The slice sorted by, and the other slices.
+    fn split_keys(&mut self) -> (&mut [Self::Key], Self::Rest<'_>);
+}
+
+macro_rules! impl_columns_for_tuple {
+    ($($rest:ident),+) => {
+        #[allow(non_snake_case)]
+        impl<'a, K, $($rest),+> Columns for (&'a mut [K], $($rest),+)
+        where
+            $($rest: Permute,)+
+        {
+            type Key = K;
+
+            type Rest<'b> = ($(&'b mut $rest,)+)
+            where
+                Self: 'b;
+
+            fn keys(&self) -> &[K] {
+                self.0
+            }
+
+            fn split_keys(&mut self) -> (&mut [K], Self::Rest<'_>) {
+                let (keys, $($rest),+) = self;
+                (&mut **keys, ($($rest,)+))
+            }
+        }
+    };
+}
+
+impl_columns_for_tuple!(A);
+impl_columns_for_tuple!(A, B);
+impl_columns_for_tuple!(A, B, C);
+impl_columns_for_tuple!(A, B, C, D);
+impl_columns_for_tuple!(A, B, C, D, E);
+
+/// Sort the keys in ascending order using the algorithm, moving the values along with them.
+///
+/// It fails without changing anything if the slices have different lengths.
+///
+/// ```rust
+/// use buldak::argsort::Algorithm;
+/// use buldak::zipped;
+///
+/// let mut keys = [3, 1, 2];
+/// let mut values = ["c", "a", "b"];
+/// zipped::sort_zipped(Algorithm::Merge, &mut keys, &mut values).unwrap();
+/// assert_eq!(keys, [1, 2, 3]);
+/// assert_eq!(values, ["a", "b", "c"]);
+/// ```
+pub fn sort_zipped<K, V>(
+    algorithm: Algorithm,
+    keys: &mut [K],
+    values: &mut [V],
+) -> Result<(), String>
+where
+    K: std::cmp::Ord,
+{
+    sort_zipped_by(algorithm, keys, values, |l, r| l.cmp(r))
+}
+
+/// It takes a comparator function to determine the order of the keys,
+/// and sorts them using the algorithm, moving the values along with them.
+///
+/// See [`sort_columns_by`] for what it allocates and which algorithms keep equal keys in order.
+///
+/// ```rust
+/// use buldak::argsort::Algorithm;
+/// use buldak::zipped;
+///
+/// let mut keys = [1, 3, 1, 2];
+/// let mut values = ["a", "d", "b", "c"];
+/// zipped::sort_zipped_by(Algorithm::Merge, &mut keys, &mut values, |l, r| l.cmp(r).reverse())
+///     .unwrap();
+/// assert_eq!(keys, [3, 2, 1, 1]);
+/// assert_eq!(values, ["d", "c", "a", "b"]);
+/// ```
+pub fn sort_zipped_by<K, V, F>(
+    algorithm: Algorithm,
+    keys: &mut [K],
+    values: &mut [V],
+    compare: F,
+) -> Result<(), String>
+where
+    F: Fn(&K, &K) -> std::cmp::Ordering,
+{
+    sort_columns_by(algorithm, &mut (keys, values), compare)
+}
+
+/// It takes a comparator function to determine the order of the keys in the first slice,
+/// and sorts them using the algorithm, moving the elements of the other slices along with them.
+///
+/// An algorithm sorts the keys in place when its implementation only compares and swaps elements:
+/// Bubble, SmartBubble, CocktailShaker, Selection, DoubleSelection, Stooge, Gnome, Comb,
+/// OddEven, Bitonic, OddEvenMerge, Quick, Heap, Pancake, Bogo and Network.
+/// That implementation runs with every swap of two keys made on the other slices too,
+/// so it allocates nothing more than sorting the keys alone does,
+/// and equal keys keep their order only if the algorithm is stable.
+/// Insertion, BinaryInsertion, Shell, Cycle, Merge, Intro, Tim and DropMerge
+/// move clones or use buffers, so they allocate an argsort of N indices and N flags to apply it,
+/// and equal keys keep the order they had whichever of them runs.
+///
+/// It fails without changing anything if the slices have different lengths.
+///
+/// ```rust
+/// use buldak::argsort::Algorithm;
+/// use buldak::zipped;
+///
+/// let mut ids = vec![30, 10, 20];
+/// let mut names = vec!["c", "a", "b"];
+/// let mut scores = vec![0.3, 0.1, 0.2];
+///
+/// zipped::sort_columns_by(
+///     Algorithm::Intro,
+///     &mut (&mut ids[..], &mut names, &mut scores),
+///     |l, r| l.cmp(r),
+/// )
+/// .unwrap();
+/// assert_eq!(ids, vec![10, 20, 30]);
+/// assert_eq!(names, vec!["a", "b", "c"]);
+/// assert_eq!(scores, vec![0.1, 0.2, 0.3]);
+///
+/// let mut short = vec!["a"];
+/// assert!(zipped::sort_columns_by(Algorithm::Intro, &mut (&mut ids[..], &mut short), |l, r| l.cmp(r)).is_err());
+/// ```
+pub fn sort_columns_by<C, F>(
+    algorithm: Algorithm,
+    columns: &mut C,
+    compare: F,
+) -> Result<(), String>
+where
+    C: Columns,
+    F: Fn(&C::Key, &C::Key) -> std::cmp::Ordering,
+{
+    let len = match columns.permute_len() {
+        Some(len) => len,
+        None => return Err("The slices have different lengths.".to_string()),
+    };
+
+    if len <= 1 {
+        return Ok(());
+    }
+
+    if _swap_sort_impl(algorithm, columns, &compare) {
+        return Ok(());
+    }
+
+    let order = crate::argsort::argsort_by(algorithm, columns.keys(), compare);
+    crate::argsort::apply_permutation(&order, columns)
+}
+
+// Runs the algorithm on the columns if its implementation only swaps elements,
+// and returns whether it did.
+fn _swap_sort_impl<C, F>(algorithm: Algorithm, columns: &mut C, compare: &F) -> bool
+where
+    C: Columns,
+    F: Fn(&C::Key, &C::Key) -> std::cmp::Ordering,
+{
+    let (keys, mut rest) = columns.split_keys();
+    let swap = |keys: &mut [C::Key], i: usize, j: usize| {
+        keys.swap(i, j);
+        rest.permute_swap(i, j);
+    };
+
+    match algorithm {
+        Algorithm::Bubble => crate::bubble::_bubble_sort_impl(keys, compare, swap),
+        Algorithm::SmartBubble => crate::smart_bubble::_smart_bubble_sort_impl(keys, compare, swap),
+        Algorithm::CocktailShaker => {
+            crate::cocktail_shaker::_cocktail_shaker_sort_impl(keys, compare, swap)
+        }
+        Algorithm::Selection => crate::selection::_selection_sort_impl(keys, compare, swap),
+        Algorithm::DoubleSelection => {
+            crate::double_selection::_double_selection_sort_impl(keys, compare, swap)
+        }
+        Algorithm::Stooge => crate::stooge::_stooge_sort_impl(keys, compare, swap),
+        Algorithm::Gnome => crate::gnome::_gnome_sort_impl(keys, compare, swap),
+        Algorithm::Comb => crate::comb::_comb_sort_impl(keys, compare, swap),
+        Algorithm::OddEven => crate::oddeven::_oddeven_sort_impl(keys, compare, swap),
+        Algorithm::Bitonic => crate::bitonic::_bitonic_sort_impl(keys, compare, swap).unwrap(),
+        Algorithm::OddEvenMerge => {
+            crate::oddeven_merge::_oddeven_merge_sort_impl(keys, compare, swap)
+        }
+        Algorithm::Quick => crate::quick::_quick_sort_impl(keys, compare, swap),
+        Algorithm::Heap => crate::heap::_heap_sort_impl(keys, compare, swap),
+        Algorithm::Pancake => {
+            crate::pancake::_pancake_sort_impl(keys, compare, swap);
+        }
+        Algorithm::Bogo => crate::bogo::_bogo_sort_impl(keys, compare, swap),
+        Algorithm::Network => crate::network::_network_sort_impl(keys, compare, swap),
+        _ => return false,
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn sort_zipped() {
+        use crate::argsort::Algorithm;
+
+        let keys: Vec<i64> = (0..500).map(|i| (i * 7919) % 101).collect();
+        let values: Vec<usize> = (0..500).collect();
+
+        let mut expected: Vec<(i64, usize)> = keys.iter().copied().zip(values.clone()).collect();
+        expected.sort_by_key(|e| e.0);
+        let (expected_keys, expected_values): (Vec<i64>, Vec<usize>) = expected.into_iter().unzip();
+
+        // The argsort keeps equal keys in order whichever algorithm sorts it.
+        let through_argsort = [
+            Algorithm::Insertion,
+            Algorithm::BinaryInsertion,
+            Algorithm::Shell,
+            Algorithm::Cycle,
+            Algorithm::Merge,
+            Algorithm::Intro,
+            Algorithm::Tim,
+            Algorithm::DropMerge,
+        ];
+        for algorithm in through_argsort {
+            let mut actual_keys = keys.clone();
+            let mut actual_values = values.clone();
+            super::sort_zipped(algorithm, &mut actual_keys, &mut actual_values).unwrap();
+            assert_eq!(actual_keys, expected_keys, "{:?}", algorithm);
+            assert_eq!(actual_values, expected_values, "{:?}", algorithm);
+        }
+
+        // The in place sorts move the values exactly as their own sort_by moves the pairs.
+        let in_place = [
+            Algorithm::Bubble,
+            Algorithm::SmartBubble,
+            Algorithm::CocktailShaker,
+            Algorithm::Selection,
+            Algorithm::DoubleSelection,
+            Algorithm::Stooge,
+            Algorithm::Gnome,
+            Algorithm::Comb,
+            Algorithm::OddEven,
+            Algorithm::Bitonic,
+            Algorithm::OddEvenMerge,
+            Algorithm::Quick,
+            Algorithm::Heap,
+            Algorithm::Pancake,
+            Algorithm::Network,
+        ];
+        for algorithm in in_place {
+            let mut pairs: Vec<(i64, usize)> = keys.iter().copied().zip(values.clone()).collect();
+            let by_key = |l: &(i64, usize), r: &(i64, usize)| l.0.cmp(&r.0);
+            match algorithm {
+                Algorithm::Bubble => crate::bubble::sort_by(&mut pairs, by_key),
+                Algorithm::SmartBubble => crate::smart_bubble::sort_by(&mut pairs, by_key),
+                Algorithm::CocktailShaker => crate::cocktail_shaker::sort_by(&mut pairs, by_key),
+                Algorithm::Selection => crate::selection::sort_by(&mut pairs, by_key),
+                Algorithm::DoubleSelection => crate::double_selection::sort_by(&mut pairs, by_key),
+                Algorithm::Stooge => crate::stooge::sort_by(&mut pairs, by_key),
+                Algorithm::Gnome => crate::gnome::sort_by(&mut pairs, by_key),
+                Algorithm::Comb => crate::comb::sort_by(&mut pairs, by_key),
+                Algorithm::OddEven => crate::oddeven::sort_by(&mut pairs, by_key),
+                Algorithm::Bitonic => crate::bitonic::sort_by(&mut pairs, by_key).unwrap(),
+                Algorithm::OddEvenMerge => crate::oddeven_merge::sort_by(&mut pairs, by_key),
+                Algorithm::Quick => crate::quick::sort_by(&mut pairs, by_key),
+                Algorithm::Heap => crate::heap::sort_by(&mut pairs, by_key),
+                Algorithm::Pancake => crate::pancake::sort_by(&mut pairs, by_key),
+                Algorithm::Network => {
+                    let mut array = [(0, 0); 500];
+                    array.copy_from_slice(&pairs);
+                    crate::network::sort_by(&mut array, by_key);
+                    pairs = array.to_vec();
+                }
+                _ => unreachable!(),
+            }
+            let (pair_keys, pair_values): (Vec<i64>, Vec<usize>) = pairs.into_iter().unzip();
+
+            let mut actual_keys = keys.clone();
+            let mut actual_values = values.clone();
+            super::sort_zipped(algorithm, &mut actual_keys, &mut actual_values).unwrap();
+            assert_eq!(actual_keys, expected_keys, "{:?}", algorithm);
+            assert_eq!(actual_keys, pair_keys, "{:?}", algorithm);
+            assert_eq!(actual_values, pair_values, "{:?}", algorithm);
+        }
+
+        let mut actual_keys = vec![2, 1, 2, 0];
+        let mut actual_values = vec!["c", "b", "d", "a"];
+        super::sort_zipped(Algorithm::Bogo, &mut actual_keys, &mut actual_values).unwrap();
+        assert_eq!(actual_keys, vec![0, 1, 2, 2]);
+        assert_eq!(&actual_values[..2], ["a", "b"]);
+
+        for algorithm in through_argsort.iter().chain(&in_place) {
+            let mut one = [1];
+            super::sort_zipped(*algorithm, &mut one, &mut ["a"]).unwrap();
+            super::sort_zipped::<i32, i32>(*algorithm, &mut [], &mut []).unwrap();
+        }
+
+        let mut actual_keys = keys.clone();
+        let mut actual_values = values.clone();
+        let mut labels: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+        super::sort_columns_by(
+            Algorithm::Merge,
+            &mut (&mut actual_keys[..], &mut actual_values, &mut labels),
+            |l, r| l.cmp(r),
+        )
+        .unwrap();
+        assert_eq!(actual_keys, expected_keys);
+        assert_eq!(actual_values, expected_values);
+        assert!(labels
+            .iter()
+            .zip(&actual_values)
+            .all(|(l, v)| *l == v.to_string()));
+
+        let mut actual_keys = keys.clone();
+        let mut short = vec![0; 3];
+        assert!(super::sort_zipped(Algorithm::Intro, &mut actual_keys, &mut short).is_err());
+        assert_eq!(actual_keys, keys);
+    }
+}